@@ -0,0 +1,104 @@
+use std::sync::Arc;
+
+use crate::persistent_list::persistent_list;
+
+persistent_list! {
+    /// The thread-safe counterpart of [`Plist`](crate::persistent_list::Plist).
+    ///
+    /// Cells are shared through `Arc`, so an `ArcPlist<T>` is `Send + Sync` when
+    /// `T` is, and threads can each hold versions of one list: cloning is O(1) and
+    /// [`ArcPlist::push_front`] shares every existing cell.
+    ArcPlist, Arc
+}
+
+impl<T> Drop for ArcPlist<T> {
+    // The same walk as `Plist`'s drop, but several threads may drop versions
+    // sharing a cell at once. `Arc::into_inner` (unlike `try_unwrap`) hands the
+    // cell to exactly one of them, so the last owner always continues the loop
+    // instead of dropping the rest of the chain recursively.
+    fn drop(&mut self) {
+        let mut node = std::mem::replace(&mut self.head, Arc::new(Node::Nil));
+        while let Some(Node::Val { next, .. }) = Arc::into_inner(node) {
+            node = next;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    fn to_vec(list: &ArcPlist<i32>) -> Vec<i32> {
+        list.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_is_send_and_sync() {
+        fn assert_send_sync<S: Send + Sync>() {}
+        assert_send_sync::<ArcPlist<i32>>();
+        assert_send_sync::<ArcPlist<String>>();
+    }
+
+    #[test]
+    fn test_push_front_shares_tail() {
+        let base: ArcPlist<i32> = (1..=3).collect();
+        let longer = base.push_front(0);
+
+        assert!(longer.tail().unwrap().ptr_eq(&base));
+        assert_eq!(to_vec(&base), vec![1, 2, 3]);
+        assert_eq!(to_vec(&longer), vec![0, 1, 2, 3]);
+        assert_eq!(longer.head(), Some(&0));
+        assert_eq!(longer.len(), 4);
+    }
+
+    #[test]
+    fn test_versions_across_threads() {
+        let base: ArcPlist<i32> = (1..=3).collect();
+
+        let handles: Vec<_> = (0..4).map(|i| {
+            let base = base.clone();
+            thread::spawn(move || {
+                let mine = base.push_front(i * 10);
+                assert!(mine.tail().unwrap().ptr_eq(&base));
+                mine.iter().sum::<i32>()
+            })
+        }).collect();
+
+        let sums: Vec<i32> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        assert_eq!(sums, vec![6, 16, 26, 36]);
+        assert_eq!(to_vec(&base), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_drop_long_list() {
+        let n = if cfg!(miri) { 1_000 } else { 2_000_000 };
+        let list = (0..n).fold(ArcPlist::new(), |list, v| list.push_front(v));
+        drop(list);
+    }
+
+    #[test]
+    fn test_drop_shared_long_list_from_threads() {
+        let n = if cfg!(miri) { 200 } else { 500_000 };
+        let list = (0..n).fold(ArcPlist::new(), |list, v| list.push_front(v));
+
+        let handles: Vec<_> = (0..4).map(|_| {
+            let version = list.push_front(-1);
+            thread::spawn(move || drop(version))
+        }).collect();
+        drop(list);
+        for h in handles {
+            h.join().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_eq_debug_display() {
+        let a: ArcPlist<i32> = (1..=2).collect();
+        let b = ArcPlist::new().push_front(2).push_front(1);
+
+        assert_eq!(a, b);
+        assert_eq!(format!("{:?}", a), "[1, 2]");
+        assert_eq!(a.to_string(), "[1 -> 2]");
+    }
+}
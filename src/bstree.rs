@@ -3,6 +3,7 @@ use std::fmt::Display;
 #[allow(dead_code)]
 pub struct Bstree<T :PartialEq+Display> {
     root : Box<Node<T>>,
+    len : usize,
 }
 
 #[allow(dead_code)]
@@ -27,10 +28,33 @@ impl<T :PartialEq+Display> PartialEq for Node<T> {
     }
 }
 
+impl<T :PartialEq+Display> Drop for Bstree<T> {
+    // Free subtrees from an explicit stack so a degenerate (list-shaped) tree
+    // does not recurse once per level.
+    fn drop(&mut self) {
+        let mut stack = vec![std::mem::replace(&mut self.root, Box::new(Node::Nil))];
+        while let Some(mut node) = stack.pop() {
+            if let Node::Value { left, right, .. } = std::mem::replace(&mut *node, Node::Nil) {
+                stack.push(left);
+                stack.push(right);
+            }
+        }
+    }
+}
+
 #[allow(dead_code)]
 impl<T :Ord+Display> Bstree<T> {
     pub fn new() -> Self {
-        Bstree { root: Box::new(Node::Nil) }
+        Bstree { root: Box::new(Node::Nil), len: 0 }
+    }
+
+    /// Returns the number of values in O(1), counting duplicates.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
     }
 
     fn empty() -> Box<Node<T>> {
@@ -38,15 +62,16 @@ impl<T :Ord+Display> Bstree<T> {
     }
 
     pub fn insert(&mut self, v :T) {
+        self.len += 1;
         if Node::Nil == *self.root {
-            self.root = Box::new(Node::Value { value: v, left: Self::empty(), right: Self::empty() });
+            *self.root = Node::Value { value: v, left: Self::empty(), right: Self::empty() };
             return;
         };
 
         let mut b = &mut self.root;
         loop {
             let Node::Value { ref value, ref mut left, ref mut right } = **b else {
-                *b = Box::new(Node::Value { value: v, left: Self::empty(), right: Self::empty() });
+                **b = Node::Value { value: v, left: Self::empty(), right: Self::empty() };
                 break
             };
 
@@ -61,20 +86,17 @@ impl<T :Ord+Display> Bstree<T> {
                     left: l,
                     right: Self::empty(),
                 };
-                *left = Box::new(new_node);
+                **left = new_node;
                 break;
             }
         }
     }
 
     fn traverse_sorted(node :&Node<T>) {
-        match *node {
-            Node::Value { ref value, ref left, ref right } => {
-                Self::traverse_sorted(left);
-                println!("{} ", *value);
-                Self::traverse_sorted(right);
-            },
-            _ => (),
+        if let Node::Value { ref value, ref left, ref right } = *node {
+            Self::traverse_sorted(left);
+            println!("{} ", *value);
+            Self::traverse_sorted(right);
         };
     }
 
@@ -82,3 +104,41 @@ impl<T :Ord+Display> Bstree<T> {
         Self::traverse_sorted(&self.root);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drop_degenerate_tree() {
+        // Same shape as inserting 0..n in sorted order, built directly to keep the test fast.
+        let mut node = Box::new(Node::Nil);
+        for i in (0..if cfg!(miri) { 1_000 } else { 2_000_000 }).rev() {
+            node = Box::new(Node::Value { value: i, left: Box::new(Node::Nil), right: node });
+        }
+        let tree = Bstree { root: node, len: 0 };
+        drop(tree);
+    }
+
+    #[test]
+    fn test_drop_after_insert() {
+        let mut tree = Bstree::new();
+        for v in [10, 4, 20, 25, 22, 10] {
+            tree.insert(v);
+        }
+        drop(tree);
+    }
+
+    #[test]
+    fn test_len() {
+        let mut tree = Bstree::new();
+        assert_eq!(tree.len(), 0);
+        assert!(tree.is_empty());
+
+        for v in [10, 4, 20, 10] {
+            tree.insert(v);
+        }
+        assert_eq!(tree.len(), 4);
+        assert!(!tree.is_empty());
+    }
+}
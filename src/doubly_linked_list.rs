@@ -1,22 +1,23 @@
 use std::rc::{Rc, Weak};
 use std::cell::RefCell;
-use std::fmt::Display;
+use std::fmt::{self, Debug, Display};
 use anyhow::{Result, bail};
 
 #[allow(dead_code)]
-pub struct Dlist<T :Display> {
+pub struct Dlist<T> {
     head :Rc<RefCell<Node<T>>>,
     tail :Weak<RefCell<Node<T>>>,
+    len :usize,
 }
 
 #[allow(dead_code)]
 #[derive(Debug)]
-enum Node<T :Display> {
+enum Node<T> {
     Value { value:T, next :Rc<RefCell<Node<T>>>, prev :Weak<RefCell<Node<T>>> },
     Nil,
 }
 
-impl<T :PartialEq+Display> PartialEq for Node<T> {
+impl<T :PartialEq> PartialEq for Node<T> {
     fn eq(&self, other: &Self) -> bool {
         match(self, other) {
             (Node::Nil, Node::Nil) => true,
@@ -30,57 +31,264 @@ impl<T :PartialEq+Display> PartialEq for Node<T> {
 
 }
 
+impl<T> Drop for Dlist<T> {
+    // Walk the strong `next` chain iteratively; the default drop recurses once per node.
+    fn drop(&mut self) {
+        let mut node = std::mem::replace(&mut self.head, Rc::new(RefCell::new(Node::Nil)));
+        while let Ok(cell) = Rc::try_unwrap(node) {
+            let Node::Value { next, .. } = cell.into_inner() else {
+                break;
+            };
+            node = next;
+        }
+    }
+}
+
+impl<T> Default for Dlist<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T :Debug> Debug for Dlist<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
 #[allow(dead_code)]
-impl<T :PartialEq+Display> Dlist<T> {
+impl<T> Dlist<T> {
     pub fn new() -> Dlist<T> {
         let node = Rc::new(RefCell::new(Node::Nil));
         Dlist{
             head: Rc::clone(&node),
             tail: Rc::downgrade(&node),
+            len: 0,
         }
     }
 
+    /// Returns the number of elements in O(1).
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
     fn empty() -> RefCell<Node<T>> {
         RefCell::new(Node::Nil)
     }
 
     pub fn insert(&mut self, value :T) {
-        if *self.head.borrow() == Node::Nil {
-            let h = Rc::new(RefCell::new(Node::Value{
-                value :value,
-                next: Rc::new(RefCell::new(Node::Nil)),
-                prev: Weak::new(),
-            }));
-            self.head = Rc::clone(&h);
-            self.tail = Rc::downgrade(&h);
+        self.push_back(value);
+    }
+
+    // Makes a single-node list; `head` and `tail` both point at the new node.
+    fn push_first(&mut self, value :T) {
+        let h = Rc::new(RefCell::new(Node::Value {
+            value,
+            next: Rc::new(Self::empty()),
+            prev: Weak::new(),
+        }));
+        self.head = Rc::clone(&h);
+        self.tail = Rc::downgrade(&h);
+        self.len += 1;
+    }
+
+    /// Appends `value` in O(1) through `tail`.
+    pub fn push_back(&mut self, value :T) {
+        if self.len == 0 {
+            self.push_first(value);
+            return;
+        }
+
+        let tail = self.tail.upgrade().expect("non-empty list has a tail");
+        let n = Rc::new(RefCell::new(Node::Value {
+            value,
+            next: Rc::new(Self::empty()),
+            prev: Rc::downgrade(&tail),
+        }));
+
+        if let Node::Value { ref mut next, .. } = *tail.borrow_mut() {
+            *next = Rc::clone(&n);
+        };
+        self.tail = Rc::downgrade(&n);
+        self.len += 1;
+    }
+
+    /// Prepends `value` in O(1).
+    pub fn push_front(&mut self, value :T) {
+        if self.len == 0 {
+            self.push_first(value);
             return;
         }
 
+        let new_node = Rc::new(RefCell::new(Node::Value {
+            value,
+            next: Rc::clone(&self.head),
+            prev: Weak::new(),
+        }));
+
+        if let Node::Value { ref mut prev, .. } = *self.head.borrow_mut() {
+            *prev = Rc::downgrade(&new_node);
+        };
+        self.head = new_node;
+        self.len += 1;
+    }
+
+    /// Inserts `val` so that it ends up at index `pos`, for `pos <= len`.
+    pub fn insert_at(&mut self, val :T, pos: usize) -> Result<()> {
+        if pos > self.len {
+            bail!("index out of bound");
+        }
+        if pos == 0 {
+            self.push_front(val);
+            return Ok(());
+        }
+
+        // Walk to the node at `pos - 1`; the new node goes right after it.
         let mut node = Rc::clone(&self.head);
+        for _ in 1..pos {
+            let n = match &*node.borrow() {
+                Node::Value { next, .. } => next.clone(),
+                _ => bail!("index out of bound"),
+            };
+            node = n;
+        }
 
-        loop {
-            let n :Rc<RefCell<Node<T>>>;
+        if self.tail.upgrade().is_some_and(|tail| Rc::ptr_eq(&tail, &node)) {
+            self.push_back(val);
+            return Ok(());
+        }
 
-            if let Node::Value { value:_, ref mut next, prev:_ } = *node.borrow_mut() {
-                if *next.borrow() != Node::Nil {
-                    n = Rc::clone(next);
-                } else {
-                    let n = Rc::new(RefCell::new(Node::Value {
-                        value: value,
-                        next: Rc::new(Self::empty()),
-                        prev: Rc::downgrade(&node),
-                    }));
-                    *next = Rc::clone(&n);
-                    self.tail = Rc::downgrade(&n);
-                    break;
-                }
-            } else {
-                unreachable!()
+        let Node::Value { value:_, ref mut next,  prev:_ } = *node.borrow_mut() else {
+            bail!("index out of bound");
+        };
+
+        let new_node = Rc::new(RefCell::new(Node::Value {
+            value: val,
+            next: Rc::clone(next),
+            prev: Rc::downgrade(&node),
+        }));
+
+        if let Node::Value { ref mut prev, .. } = *next.borrow_mut() {
+            *prev = Rc::downgrade(&new_node);
+        };
+
+        *next = new_node;
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Removes the element at `pos` and returns it.
+    pub fn delete_nth(&mut self, pos: usize) -> Result<T> {
+        self.remove(pos)
+    }
+
+    /// Removes the element at `i` and returns it.
+    pub fn remove(&mut self, i: usize) -> Result<T> {
+        if i >= self.len {
+            bail!("index out of bound");
+        }
+
+        let mut node = Rc::clone(&self.head);
+        for _ in 0..i {
+            let n = match *node.borrow() {
+                Node::Value { ref next, .. } => Rc::clone(next),
+                _ => bail!("index out of bound"),
             };
             node = n;
         }
+
+        Ok(self.unlink(node))
+    }
+
+    /// Removes the first element and returns it, or `None` if the list is empty.
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        let head = Rc::clone(&self.head);
+        Some(self.unlink(head))
+    }
+
+    /// Removes the last element and returns it, or `None` if the list is empty.
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        let tail = self.tail.upgrade()?;
+        Some(self.unlink(tail))
     }
 
+    // Detaches `node` from its neighbours and moves its value out. `node`
+    // must be a `Value` node of this list.
+    fn unlink(&mut self, node: Rc<RefCell<Node<T>>>) -> T {
+        let (next, prev) = match *node.borrow() {
+            Node::Value { ref next, ref prev, .. } => (Rc::clone(next), prev.clone()),
+            Node::Nil => unreachable!("unlink called on Nil"),
+        };
+
+        // Update prev node's next pointer, or update head if deleting first node
+        if let Some(prev_rc) = prev.upgrade() {
+            if let Node::Value { next: ref mut prev_next, .. } = *prev_rc.borrow_mut() {
+                *prev_next = Rc::clone(&next);
+            }
+        } else {
+            self.head = Rc::clone(&next);
+        }
+
+        // Update next node's prev pointer, or update tail if deleting last node
+        if let Node::Value { prev: ref mut next_prev, .. } = *next.borrow_mut() {
+            *next_prev = prev;
+        } else {
+            self.tail = prev;
+        }
+
+        self.len -= 1;
+        if self.len == 0 {
+            // Keep head and tail on the same Nil node, as in `new`.
+            self.tail = Rc::downgrade(&self.head);
+        }
+
+        // The neighbours only hold weak or replaced links now, so `node` is
+        // the last strong reference.
+        let cell = Rc::try_unwrap(node).ok().expect("unlinked node is uniquely owned");
+        match cell.into_inner() {
+            Node::Value { value, .. } => value,
+            Node::Nil => unreachable!(),
+        }
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        let back = if self.len == 0 {
+            None
+        } else {
+            // SAFETY: a non-empty list keeps its tail alive through the
+            // previous node's `next` (or `head`) for as long as `self` is borrowed.
+            Some(unsafe { &*self.tail.as_ptr() })
+        };
+        Iter { front: Some(&*self.head), back, len: self.len }
+    }
+
+    /// Calls `f` on every value in order, with mutable access through each node's `RefCell`.
+    pub fn for_each_mut<F: FnMut(&mut T)>(&mut self, mut f: F) {
+        let mut node = Rc::clone(&self.head);
+        loop {
+            let next = match *node.borrow_mut() {
+                Node::Value { ref mut value, ref next, .. } => {
+                    f(value);
+                    Rc::clone(next)
+                },
+                Node::Nil => break,
+            };
+            node = next;
+        }
+    }
+}
+
+impl<T :Display> Dlist<T> {
     pub fn print(&mut self) {
         let mut node = Rc::clone( &self.head);
 
@@ -120,84 +328,115 @@ impl<T :PartialEq+Display> Dlist<T> {
             node = wc;
         }
     }
+}
 
-    pub fn insert_at(&mut self, val :T, pos: u32) -> Result<()> {
-        let mut node = Rc::clone(&self.head);
+/// Borrowing iterator over a [`Dlist`], created by [`Dlist::iter`].
+///
+/// Values are handed out as plain references: every mutation of a `Dlist`
+/// goes through `&mut self`, so no node's `RefCell` is mutably borrowed while
+/// the list is shared for 'a.
+pub struct Iter<'a, T> {
+    front: Option<&'a RefCell<Node<T>>>,
+    back: Option<&'a RefCell<Node<T>>>,
+    len: usize,
+}
 
-        for _ in 1..pos {
-            let n = match &*node.borrow() {
-                Node::Value { next, .. } => next.clone(),
-                _ => bail!("index out of bound"),
-            };
-            node = n;
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
         }
+        // SAFETY: the list is borrowed shared for 'a, so the cell is never
+        // mutably borrowed while the reference lives (see `Iter`).
+        let Node::Value { value, next, .. } = (unsafe { self.front?.try_borrow_unguarded() }).ok()? else {
+            return None;
+        };
+        self.front = Some(&**next);
+        self.len -= 1;
+        Some(value)
+    }
 
-        if *node.borrow() == *self.head.borrow() {
-            let new_node = Rc::new(RefCell::new(Node::Value {
-                value: val,
-                next: Rc::clone(&self.head),
-                prev: Weak::new(),
-            }));
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
 
-            if let Node::Value {  ref mut prev,.. } = *self.head.borrow_mut() {
-                *prev = Rc::downgrade(&new_node.clone());
-            };
-            self.head = new_node;
-            return Ok(());
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
         }
-
-        let Node::Value { value:_, ref mut next,  prev:_ } = *node.borrow_mut() else {
-            bail!("index out of bound");
+        // SAFETY: as in `next`.
+        let Node::Value { value, prev, .. } = (unsafe { self.back?.try_borrow_unguarded() }).ok()? else {
+            return None;
         };
+        self.len -= 1;
+        if self.len > 0 {
+            // SAFETY: `prev` is a live node of the list, owned by its own
+            // predecessor's `next` or by `head`.
+            self.back = Some(unsafe { &*prev.as_ptr() });
+        }
+        Some(value)
+    }
+}
 
-        let new_node = Rc::new(RefCell::new(Node::Value {
-            value: val,
-            next: Rc::clone(next),
-            prev: Rc::downgrade(&node),
-        }));
+impl<T> ExactSizeIterator for Iter<'_, T> {}
 
-        if let Node::Value { ref mut prev, .. } = *next.borrow_mut() {
-            *prev = Rc::downgrade(&new_node);
-        } else {
-            // next is Node::NIl, so make new node the tail
-            self.tail = Rc::downgrade(&new_node);
-        };
+pub struct IntoIter<T>(Dlist<T>);
 
-        *next = new_node.clone();
-        Ok(())
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop_front()
     }
 
-    pub fn delete_nth(&mut self, pos: u32) -> Result<()> {
-        let mut node = Rc::clone(&self.head);
-        for _ in 0..pos {
-            let n = match *node.borrow() {
-                Node::Value { ref next, .. } => Rc::clone(next),
-                _ => bail!("index out of bound"),
-            };
-            node = n;
-        }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len(), Some(self.0.len()))
+    }
+}
 
-        let Node::Value { ref next, ref prev, .. } = *node.borrow() else {
-            bail!("index out of bound");
-        };
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.pop_back()
+    }
+}
 
-        // Update prev node's next pointer, or update head if deleting first node
-        if let Some(prev_rc) = prev.upgrade() {
-            if let Node::Value { next: ref mut prev_next, .. } = *prev_rc.borrow_mut() {
-                *prev_next = next.clone();
-            }
-        } else {
-            self.head = next.clone();
-        }
+impl<T> ExactSizeIterator for IntoIter<T> {}
 
-        // Update next node's prev pointer, or update tail if deleting last node
-        if let Node::Value { prev: ref mut next_prev, .. } = *next.borrow_mut() {
-            *next_prev = prev.clone();
-        } else {
-            self.tail = prev.clone();
+impl<T> IntoIterator for Dlist<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Dlist<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> Extend<T> for Dlist<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for v in iter {
+            self.push_back(v);
         }
+    }
+}
 
-        Ok(())
+impl<T> FromIterator<T> for Dlist<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Dlist::new();
+        list.extend(iter);
+        list
     }
 }
 
@@ -409,22 +648,362 @@ mod tests {
         list.insert(30);
 
         // Navigate to tail and check prev links back
-        if let Some(tail_rc) = list.tail.upgrade() {
-            if let Node::Value { value, prev, .. } = &*tail_rc.borrow() {
-                assert_eq!(*value, 30);
-                // Prev should be 20
-                if let Some(prev_rc) = prev.upgrade() {
-                    if let Node::Value { value, prev, .. } = &*prev_rc.borrow() {
-                        assert_eq!(*value, 20);
-                        // Prev should be 10
-                        if let Some(prev_rc) = prev.upgrade() {
-                            if let Node::Value { value, .. } = &*prev_rc.borrow() {
-                                assert_eq!(*value, 10);
-                            }
-                        }
-                    }
+        if let Some(tail_rc) = list.tail.upgrade()
+            && let Node::Value { value, prev, .. } = &*tail_rc.borrow()
+        {
+            assert_eq!(*value, 30);
+            // Prev should be 20
+            if let Some(prev_rc) = prev.upgrade()
+                && let Node::Value { value, prev, .. } = &*prev_rc.borrow()
+            {
+                assert_eq!(*value, 20);
+                // Prev should be 10
+                if let Some(prev_rc) = prev.upgrade()
+                    && let Node::Value { value, .. } = &*prev_rc.borrow()
+                {
+                    assert_eq!(*value, 10);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_drop_long_list() {
+        let mut head = Rc::new(RefCell::new(Node::Nil));
+        for i in 0..if cfg!(miri) { 1_000 } else { 2_000_000 } {
+            head = Rc::new(RefCell::new(Node::Value { value: i, next: head, prev: Weak::new() }));
+        }
+        let list = Dlist { head, tail: Weak::new(), len: 0 };
+        drop(list);
+    }
+
+    #[test]
+    fn test_len() {
+        let mut list: Dlist<i32> = Dlist::new();
+        assert_eq!(list.len(), 0);
+        assert!(list.is_empty());
+
+        list.insert(10);
+        list.insert(20);
+        list.insert_at(5, 0).unwrap();
+        list.insert_at(30, 3).unwrap();
+        assert_eq!(list.len(), 4);
+
+        list.delete_nth(3).unwrap();
+        list.delete_nth(0).unwrap();
+        assert_eq!(list.len(), 2);
+        assert!(!list.is_empty());
+    }
+
+    #[test]
+    fn test_len_rejects_out_of_range() {
+        let mut list: Dlist<i32> = Dlist::new();
+        list.insert(10);
+
+        assert!(list.insert_at(1, 2).is_err());
+        assert!(list.delete_nth(1).is_err());
+        assert_eq!(list.len(), 1);
+
+        list.delete_nth(0).unwrap();
+        assert!(list.is_empty());
+        assert!(list.delete_nth(0).is_err());
+    }
+
+    fn values(list: &Dlist<i32>) -> Vec<i32> {
+        let mut out = Vec::new();
+        let mut node = Rc::clone(&list.head);
+        loop {
+            let next = match *node.borrow() {
+                Node::Value { value, ref next, .. } => {
+                    out.push(value);
+                    Rc::clone(next)
+                },
+                Node::Nil => break,
+            };
+            node = next;
+        }
+        out
+    }
+
+    fn values_reverse(list: &Dlist<i32>) -> Vec<i32> {
+        let mut out = Vec::new();
+        let mut node = list.tail.upgrade();
+        while let Some(rc) = node {
+            let Node::Value { value, ref prev, .. } = *rc.borrow() else {
+                break;
+            };
+            out.push(value);
+            node = prev.upgrade();
+        }
+        out
+    }
+
+    #[test]
+    fn test_push_back() {
+        let mut list: Dlist<i32> = Dlist::new();
+        list.push_back(1);
+        list.push_back(2);
+        list.push_back(3);
+
+        assert_eq!(values(&list), vec![1, 2, 3]);
+        assert_eq!(values_reverse(&list), vec![3, 2, 1]);
+        assert_eq!(list.len(), 3);
+    }
+
+    #[test]
+    fn test_push_front() {
+        let mut list: Dlist<i32> = Dlist::new();
+        list.push_front(2);
+        list.push_front(1);
+        list.push_back(3);
+        list.push_front(0);
+
+        assert_eq!(values(&list), vec![0, 1, 2, 3]);
+        assert_eq!(values_reverse(&list), vec![3, 2, 1, 0]);
+        assert_eq!(list.len(), 4);
+    }
+
+    #[test]
+    fn test_push_back_after_insert_at_on_empty_list() {
+        let mut list: Dlist<i32> = Dlist::new();
+        list.insert_at(1, 0).unwrap();
+        list.push_back(2);
+
+        assert_eq!(values(&list), vec![1, 2]);
+        assert_eq!(values_reverse(&list), vec![2, 1]);
+    }
+
+    #[test]
+    fn test_push_back_after_delete() {
+        let mut list: Dlist<i32> = Dlist::new();
+        list.insert(1);
+        list.insert(2);
+        list.delete_nth(1).unwrap();
+        list.push_back(3);
+        list.delete_nth(0).unwrap();
+        list.delete_nth(0).unwrap();
+        list.push_back(4);
+
+        assert_eq!(values(&list), vec![4]);
+        assert_eq!(values_reverse(&list), vec![4]);
+    }
+
+    #[test]
+    fn test_build_long_list() {
+        let n = if cfg!(miri) { 500 } else { 200_000 };
+        let mut list: Dlist<i32> = Dlist::new();
+        for i in 0..n {
+            list.insert(i);
+        }
+        assert_eq!(list.len(), n as usize);
+        assert_eq!(values_reverse(&list).first(), Some(&(n - 1)));
+    }
+
+    #[test]
+    fn test_delete_nth_returns_value() {
+        let mut list: Dlist<i32> = Dlist::new();
+        list.insert(1);
+        list.insert(2);
+        list.insert(3);
+
+        assert_eq!(list.delete_nth(1).unwrap(), 2);
+        assert_eq!(list.remove(1).unwrap(), 3);
+        assert_eq!(values(&list), vec![1]);
+        assert_eq!(values_reverse(&list), vec![1]);
+        assert!(list.remove(1).is_err());
+    }
+
+    #[test]
+    fn test_pop_front() {
+        let mut list: Dlist<i32> = Dlist::new();
+        assert_eq!(list.pop_front(), None);
+        list.insert(1);
+        list.insert(2);
+
+        assert_eq!(list.pop_front(), Some(1));
+        assert_eq!(values_reverse(&list), vec![2]);
+        assert_eq!(list.pop_front(), Some(2));
+        assert_eq!(list.pop_front(), None);
+        assert!(list.is_empty());
+
+        list.push_back(3);
+        assert_eq!(values(&list), vec![3]);
+        assert_eq!(values_reverse(&list), vec![3]);
+    }
+
+    #[test]
+    fn test_pop_back() {
+        let mut list: Dlist<i32> = Dlist::new();
+        assert_eq!(list.pop_back(), None);
+        list.insert(1);
+        list.insert(2);
+        list.insert(3);
+
+        assert_eq!(list.pop_back(), Some(3));
+        assert_eq!(values(&list), vec![1, 2]);
+        assert_eq!(values_reverse(&list), vec![2, 1]);
+        assert_eq!(list.pop_back(), Some(2));
+        assert_eq!(list.pop_back(), Some(1));
+        assert_eq!(list.pop_back(), None);
+        assert_eq!(list.len(), 0);
+
+        list.push_front(4);
+        assert_eq!(values_reverse(&list), vec![4]);
+    }
+
+    #[test]
+    fn test_pop_moves_non_copy_values() {
+        let mut list: Dlist<String> = Dlist::new();
+        list.push_back("a".to_string());
+        list.push_back("b".to_string());
+        list.push_back("c".to_string());
+
+        assert_eq!(list.pop_back().as_deref(), Some("c"));
+        assert_eq!(list.remove(0).unwrap(), "a");
+        assert_eq!(list.pop_front().as_deref(), Some("b"));
+        assert_eq!(list.pop_front(), None);
+    }
+
+    #[test]
+    fn test_insert_at_every_position() {
+        for len in 0..5 {
+            for pos in 0..=len {
+                let mut list: Dlist<i32> = Dlist::new();
+                for i in 0..len {
+                    list.push_back(i);
                 }
+                list.insert_at(99, pos as usize).unwrap();
+
+                let mut expected: Vec<i32> = (0..len).collect();
+                expected.insert(pos as usize, 99);
+                assert_eq!(values(&list), expected, "len {len}, pos {pos}");
+                expected.reverse();
+                assert_eq!(values_reverse(&list), expected, "len {len}, pos {pos}");
+                assert_eq!(list.len(), len as usize + 1);
             }
         }
     }
+
+    #[test]
+    fn test_insert_at_one_with_equal_values() {
+        // Equal values used to make the head check match the wrong node.
+        let mut list: Dlist<i32> = Dlist::new();
+        list.push_back(7);
+        list.push_back(7);
+        list.insert_at(1, 1).unwrap();
+
+        assert_eq!(values(&list), vec![7, 1, 7]);
+        assert_eq!(values_reverse(&list), vec![7, 1, 7]);
+    }
+
+    #[test]
+    fn test_insert_at_end_of_empty_list_sets_tail() {
+        let mut list: Dlist<i32> = Dlist::new();
+        list.insert_at(1, 0).unwrap();
+        list.insert_at(2, 1).unwrap();
+        list.insert_at(3, 2).unwrap();
+
+        assert_eq!(values(&list), vec![1, 2, 3]);
+        assert_eq!(values_reverse(&list), vec![3, 2, 1]);
+        assert!(list.insert_at(4, 4).is_err());
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[test]
+    fn test_non_display_values() {
+        let mut list: Dlist<Point> = Dlist::new();
+        list.push_back(Point { x: 1, y: 2 });
+        list.push_front(Point { x: 0, y: 0 });
+
+        assert_eq!(format!("{:?}", list), "[Point { x: 0, y: 0 }, Point { x: 1, y: 2 }]");
+        assert_eq!(list.pop_back(), Some(Point { x: 1, y: 2 }));
+
+        let mut shared: Dlist<Rc<(u8, char)>> = Dlist::new();
+        let item = Rc::new((1, 'a'));
+        shared.push_back(Rc::clone(&item));
+        assert_eq!(Rc::strong_count(&item), 2);
+        drop(shared);
+        assert_eq!(Rc::strong_count(&item), 1);
+    }
+
+    #[test]
+    fn test_debug() {
+        let mut list: Dlist<i32> = Dlist::new();
+        assert_eq!(format!("{:?}", list), "[]");
+        list.push_back(1);
+        list.push_back(2);
+        assert_eq!(format!("{:?}", list), "[1, 2]");
+    }
+
+    #[test]
+    fn test_iter() {
+        let list: Dlist<i32> = (1..=4).collect();
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert_eq!(list.iter().rev().copied().collect::<Vec<_>>(), vec![4, 3, 2, 1]);
+        assert_eq!(list.iter().len(), 4);
+
+        let mut sum = 0;
+        for v in &list {
+            sum += v;
+        }
+        assert_eq!(sum, 10);
+
+        let empty: Dlist<i32> = Dlist::new();
+        assert_eq!(empty.iter().next(), None);
+        assert_eq!(empty.iter().next_back(), None);
+    }
+
+    #[test]
+    fn test_iter_both_ends_meet() {
+        let list: Dlist<i32> = (1..=5).collect();
+        let mut it = list.iter();
+        assert_eq!(it.next(), Some(&1));
+        assert_eq!(it.next_back(), Some(&5));
+        assert_eq!(it.len(), 3);
+        assert_eq!(it.next_back(), Some(&4));
+        assert_eq!(it.next(), Some(&2));
+        assert_eq!(it.next(), Some(&3));
+        assert_eq!(it.next(), None);
+        assert_eq!(it.next_back(), None);
+    }
+
+    #[test]
+    fn test_iter_zip() {
+        let a: Dlist<i32> = (1..=3).collect();
+        let b: Dlist<&str> = ["a", "b", "c"].into_iter().collect();
+        let pairs: Vec<_> = a.iter().zip(b.iter().rev()).map(|(x, y)| (*x, *y)).collect();
+        assert_eq!(pairs, vec![(1, "c"), (2, "b"), (3, "a")]);
+    }
+
+    #[test]
+    fn test_for_each_mut() {
+        let mut list: Dlist<i32> = (1..=3).collect();
+        list.for_each_mut(|v| *v *= 10);
+        assert_eq!(values(&list), vec![10, 20, 30]);
+        assert_eq!(values_reverse(&list), vec![30, 20, 10]);
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let list: Dlist<String> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+        let mut it = list.into_iter();
+        assert_eq!(it.len(), 3);
+        assert_eq!(it.next_back().as_deref(), Some("c"));
+        assert_eq!(it.collect::<Vec<_>>(), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_extend() {
+        let mut list: Dlist<i32> = Dlist::new();
+        list.push_back(1);
+        list.extend(vec![2, 3]);
+        assert_eq!(values(&list), vec![1, 2, 3]);
+        assert_eq!(values_reverse(&list), vec![3, 2, 1]);
+        assert_eq!(list.len(), 3);
+    }
 }
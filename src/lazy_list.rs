@@ -0,0 +1,324 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::doubly_linked_list::Dlist;
+use crate::linked_list::List;
+
+/// A lazily evaluated, possibly infinite list.
+///
+/// Each cell is a `Val { value, next }` node or the `Nil` end, like a
+/// [`Plist`](crate::persistent_list::Plist) cell, except that it starts out as
+/// a thunk: it is computed the first time it is looked at and memoized, so
+/// later reads, and every clone of the list, see the same value. `take`,
+/// `map`, `filter` and `zip` build new lazy lists without forcing anything.
+pub struct LazyList<T> {
+    cell: Rc<RefCell<Thunk<T>>>,
+}
+
+enum Node<T> {
+    Val { value: T, next: LazyList<T> },
+    Nil,
+}
+
+enum Thunk<T> {
+    Pending(Box<dyn FnOnce() -> Node<T>>),
+    Forced(Rc<Node<T>>),
+    // Left behind while the cell is being computed or torn down.
+    Taken,
+}
+
+impl<T> Clone for LazyList<T> {
+    fn clone(&self) -> Self {
+        LazyList { cell: Rc::clone(&self.cell) }
+    }
+}
+
+impl<T> Drop for LazyList<T> {
+    // A forced list is a chain of `Rc`s; unlink the cells only this handle
+    // owns one at a time so dropping a long list does not recurse.
+    fn drop(&mut self) {
+        let mut node = self.detach();
+        while let Some(Node::Val { mut next, .. }) = node {
+            node = next.detach();
+        }
+    }
+}
+
+impl<T> LazyList<T> {
+    // Takes the forced cell out if this is its only handle.
+    fn detach(&mut self) -> Option<Node<T>> {
+        if Rc::strong_count(&self.cell) != 1 {
+            return None;
+        }
+        let Thunk::Forced(node) = std::mem::replace(&mut *self.cell.borrow_mut(), Thunk::Taken) else {
+            return None;
+        };
+        Rc::try_unwrap(node).ok()
+    }
+
+    fn force(&self) -> Rc<Node<T>> {
+        if let Thunk::Forced(ref node) = *self.cell.borrow() {
+            return Rc::clone(node);
+        }
+
+        // The thunk runs with the cell released, so it may read other cells.
+        let Thunk::Pending(f) = std::mem::replace(&mut *self.cell.borrow_mut(), Thunk::Taken) else {
+            panic!("lazy list cell forced while it was being computed");
+        };
+        let node = Rc::new(f());
+        *self.cell.borrow_mut() = Thunk::Forced(Rc::clone(&node));
+        node
+    }
+
+    /// Returns `true` if the list has no elements. Forces the first cell.
+    pub fn is_empty(&self) -> bool {
+        matches!(*self.force(), Node::Nil)
+    }
+
+    /// Returns the first element, forcing the first cell.
+    pub fn head(&self) -> Option<T> where T: Clone {
+        let Node::Val { ref value, .. } = *self.force() else {
+            return None;
+        };
+        Some(value.clone())
+    }
+
+    /// Returns the list after the first element, forcing only the first cell.
+    pub fn tail(&self) -> Option<LazyList<T>> {
+        let Node::Val { ref next, .. } = *self.force() else {
+            return None;
+        };
+        Some(next.clone())
+    }
+
+    /// Iterates over clones of the elements, forcing cells as it goes.
+    pub fn iter(&self) -> Iter<T> {
+        Iter { list: self.clone() }
+    }
+}
+
+impl<T: 'static> LazyList<T> {
+    fn from_thunk<F: FnOnce() -> Node<T> + 'static>(f: F) -> Self {
+        LazyList { cell: Rc::new(RefCell::new(Thunk::Pending(Box::new(f)))) }
+    }
+
+    fn from_node(node: Node<T>) -> Self {
+        LazyList { cell: Rc::new(RefCell::new(Thunk::Forced(Rc::new(node)))) }
+    }
+
+    pub fn empty() -> Self {
+        Self::from_node(Node::Nil)
+    }
+
+    pub fn cons(value: T, next: LazyList<T>) -> Self {
+        Self::from_node(Node::Val { value, next })
+    }
+
+    /// Builds a list from `seed`: each cell calls `f` on the current state,
+    /// which yields the element and the next state, or `None` to end the list.
+    pub fn unfold<S: 'static, F>(seed: S, f: F) -> Self
+    where F: Fn(S) -> Option<(T, S)> + 'static {
+        Self::unfold_rc(seed, Rc::new(f))
+    }
+
+    fn unfold_rc<S: 'static>(seed: S, f: Rc<dyn Fn(S) -> Option<(T, S)>>) -> Self {
+        Self::from_thunk(move || match f(seed) {
+            Some((value, seed)) => Node::Val { value, next: Self::unfold_rc(seed, f) },
+            None => Node::Nil,
+        })
+    }
+
+    /// The infinite list `seed, f(seed), f(f(seed)), ...`.
+    pub fn iterate<F: Fn(&T) -> T + 'static>(seed: T, f: F) -> Self {
+        Self::unfold(seed, move |v| {
+            let next = f(&v);
+            Some((v, next))
+        })
+    }
+
+    /// The first `n` elements.
+    pub fn take(&self, n: usize) -> Self where T: Clone {
+        let list = self.clone();
+        Self::from_thunk(move || {
+            if n == 0 {
+                return Node::Nil;
+            }
+            match *list.force() {
+                Node::Val { ref value, ref next } => Node::Val { value: value.clone(), next: next.take(n - 1) },
+                Node::Nil => Node::Nil,
+            }
+        })
+    }
+
+    pub fn map<U: 'static, F: Fn(&T) -> U + 'static>(&self, f: F) -> LazyList<U> {
+        self.map_rc(Rc::new(f))
+    }
+
+    fn map_rc<U: 'static>(&self, f: Rc<dyn Fn(&T) -> U>) -> LazyList<U> {
+        let list = self.clone();
+        LazyList::from_thunk(move || match *list.force() {
+            Node::Val { ref value, ref next } => Node::Val { value: f(value), next: next.map_rc(Rc::clone(&f)) },
+            Node::Nil => Node::Nil,
+        })
+    }
+
+    /// The elements matching `pred`. Forcing a cell skips ahead to the next
+    /// match, so on an infinite list with no further match it never returns.
+    pub fn filter<P: Fn(&T) -> bool + 'static>(&self, pred: P) -> Self where T: Clone {
+        self.filter_rc(Rc::new(pred))
+    }
+
+    fn filter_rc(&self, pred: Rc<dyn Fn(&T) -> bool>) -> Self where T: Clone {
+        let list = self.clone();
+        Self::from_thunk(move || {
+            let mut node = list.force();
+            loop {
+                let next = match *node {
+                    Node::Val { ref value, ref next } => {
+                        if pred(value) {
+                            return Node::Val { value: value.clone(), next: next.filter_rc(pred) };
+                        }
+                        next.force()
+                    },
+                    Node::Nil => return Node::Nil,
+                };
+                node = next;
+            }
+        })
+    }
+
+    /// Pairs up elements of both lists, ending with the shorter one.
+    pub fn zip<U: Clone + 'static>(&self, other: &LazyList<U>) -> LazyList<(T, U)> where T: Clone {
+        let (left, right) = (self.clone(), other.clone());
+        LazyList::from_thunk(move || match (&*left.force(), &*right.force()) {
+            (Node::Val { value: a, next: na }, Node::Val { value: b, next: nb }) => {
+                Node::Val { value: (a.clone(), b.clone()), next: na.zip(nb) }
+            },
+            _ => Node::Nil,
+        })
+    }
+
+    /// Forces the whole list into a [`List`]. Never returns for an infinite list.
+    pub fn to_list(&self) -> List<T> where T: Clone {
+        self.iter().collect()
+    }
+
+    /// Forces the whole list into a [`Dlist`]. Never returns for an infinite list.
+    pub fn to_dlist(&self) -> Dlist<T> where T: Clone {
+        self.iter().collect()
+    }
+}
+
+pub struct Iter<T> {
+    list: LazyList<T>,
+}
+
+impl<T: Clone> Iterator for Iter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.list.force();
+        let Node::Val { ref value, ref next } = *node else {
+            return None;
+        };
+        self.list = next.clone();
+        Some(value.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    fn naturals() -> LazyList<u64> {
+        LazyList::iterate(0, |n| n + 1)
+    }
+
+    #[test]
+    fn test_empty_and_cons() {
+        let empty: LazyList<i32> = LazyList::empty();
+        assert!(empty.is_empty());
+        assert_eq!(empty.head(), None);
+        assert!(empty.tail().is_none());
+
+        let list = LazyList::cons(1, LazyList::cons(2, LazyList::empty()));
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(list.tail().unwrap().head(), Some(2));
+    }
+
+    #[test]
+    fn test_infinite_take() {
+        let list = naturals().take(5);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+        assert_eq!(naturals().take(0).iter().count(), 0);
+    }
+
+    #[test]
+    fn test_unfold_finite() {
+        let countdown = LazyList::unfold(3, |n| (n > 0).then(|| (n, n - 1)));
+        assert_eq!(countdown.iter().collect::<Vec<_>>(), vec![3, 2, 1]);
+        assert_eq!(countdown.take(10).iter().count(), 3);
+    }
+
+    #[test]
+    fn test_cells_are_memoized() {
+        let calls = Rc::new(Cell::new(0));
+        let counter = Rc::clone(&calls);
+        let list = LazyList::unfold(0, move |n| {
+            counter.set(counter.get() + 1);
+            Some((n, n + 1))
+        });
+        assert_eq!(calls.get(), 0);
+
+        assert_eq!(list.take(3).iter().collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert_eq!(calls.get(), 3);
+
+        let copy = list.clone();
+        assert_eq!(copy.take(3).iter().collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert_eq!(list.head(), Some(0));
+        assert_eq!(calls.get(), 3);
+    }
+
+    #[test]
+    fn test_combinators_are_lazy() {
+        let calls = Rc::new(Cell::new(0));
+        let counter = Rc::clone(&calls);
+        let squares = naturals().map(move |n| {
+            counter.set(counter.get() + 1);
+            n * n
+        });
+        let even_squares = squares.filter(|n| n % 2 == 0);
+        let pairs = even_squares.zip(&naturals());
+        assert_eq!(calls.get(), 0);
+
+        let firsts: Vec<(u64, u64)> = pairs.take(3).iter().collect();
+        assert_eq!(firsts, vec![(0, 0), (4, 1), (16, 2)]);
+        assert_eq!(calls.get(), 5);
+    }
+
+    #[test]
+    fn test_zip_ends_with_shorter() {
+        let letters = LazyList::cons('a', LazyList::cons('b', LazyList::empty()));
+        let zipped = naturals().zip(&letters);
+        assert_eq!(zipped.iter().collect::<Vec<_>>(), vec![(0, 'a'), (1, 'b')]);
+    }
+
+    #[test]
+    fn test_to_list_and_dlist() {
+        let list = naturals().take(4).to_list();
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+        assert_eq!(list.last(), Some(&3));
+
+        let dlist = naturals().map(|n| *n as i32).take(3).to_dlist();
+        assert_eq!(dlist.len(), 3);
+    }
+
+    #[test]
+    fn test_drop_long_forced_list() {
+        let n = if cfg!(miri) { 1_000 } else { 1_000_000 };
+        let list = naturals().take(n);
+        assert_eq!(list.iter().count(), n);
+        drop(list);
+    }
+}
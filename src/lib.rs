@@ -1 +1,5 @@
 pub mod doubly_linked_list;
+pub mod linked_list;
+pub mod persistent_list;
+pub mod arc_persistent_list;
+pub mod lazy_list;
@@ -1,272 +1,2384 @@
-use std::ops::Index;
-use std::fmt::{Display, Write};
+use std::any::Any;
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::ops::{Index, IndexMut};
+use std::panic::{self, AssertUnwindSafe};
+use std::fmt::Display;
+use std::ptr::NonNull;
+use std::sync::Mutex;
 
+/// Errors returned by the fallible positional operations of [`List`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListError {
+    /// `index` does not address a valid position in a list of `len` elements.
+    IndexOutOfRange { index: usize, len: usize },
+}
+
+impl Display for ListError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            ListError::IndexOutOfRange { index, len } => {
+                write!(f, "index {} out of range for list of length {}", index, len)
+            },
+        }
+    }
+}
+
+impl std::error::Error for ListError {}
+
+/// A singly linked list with an O(1) pointer to its last node.
+///
+/// # Invariants
+///
+/// Every `unsafe` block in this module relies on these:
+///
+/// 1. Each node reachable from `head` was allocated with `Box::new` and turned
+///    into a `NonNull` with `Box::leak`. It is owned by exactly one list and is
+///    freed exactly once, with `Box::from_raw`, after being unlinked.
+/// 2. `head` and `tail` are both `None` or both `Some`. When `Some`, `tail` is
+///    the last node reachable from `head`, and its `next` is `None`.
+///    `len` is the number of nodes reachable from `head`.
+/// 3. Node pointers are only turned into references for as long as the list
+///    itself is borrowed (`&self` for `&T`, `&mut self` for `&mut T`), and no
+///    reference to a node is held while links are rewritten.
+/// 4. `finger`, when set to `(i, node)`, has `node` as the node at index `i`.
+///    Every operation that links or unlinks nodes at or before `i` clears it,
+///    and a list that gives its nodes to another list clears it too.
+/// 5. Invariants 1, 2 and 4 hold whenever user code runs: a comparator,
+///    predicate or key function, or a `T`'s `Drop`. Any of these may panic,
+///    and the list is still used (and dropped) after the unwind. Methods call
+///    out only between complete link updates, except `sort_by`, which puts
+///    every node back into one chain before resuming the panic.
+///
+/// # Finger cache
+///
+/// The list remembers the last position looked up by index, so that reading
+/// `list[i]`, `list[i + 1]`, ... walks from there instead of from `head`.
+/// Lookups only take `&self`, so the cache is interior mutability: a `Mutex`
+/// stored inline in every list. That costs the mutex and a cached
+/// `(usize, pointer)` pair per list, plus an uncontended `try_lock` per
+/// indexed lookup. A lookup that finds the mutex held simply goes uncached.
+/// See the `Hash` impl for what this means for map keys.
+///
+/// Links are raw pointers rather than `Box`es so that `tail` and cursor
+/// positions are never invalidated by moving a `Box` that owns the node they
+/// point at. The unit tests are meant to pass under Miri with both aliasing
+/// models: `cargo +nightly miri test`, with and without
+/// `MIRIFLAGS=-Zmiri-tree-borrows`.
 pub struct List<T> {
-    head: Box<Node<T>>,
-    tail: *mut Node<T>,
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+    // See "Finger cache" above. A mutex rather than a `Cell` because `List` is `Sync`.
+    finger: Mutex<Option<(usize, NonNull<Node<T>>)>>,
+    marker: PhantomData<Box<Node<T>>>,
+}
+
+type Link<T> = Option<NonNull<Node<T>>>;
+
+struct Node<T> {
+    value: T,
+    next: Link<T>,
+}
+
+impl<T> Node<T> {
+    fn alloc(value: T, next: Link<T>) -> NonNull<Node<T>> {
+        NonNull::from(Box::leak(Box::new(Node { value, next })))
+    }
+
+    /// # Safety
+    ///
+    /// `node` must come from [`Node::alloc`], be unlinked from every list, and
+    /// not be used again.
+    unsafe fn free(node: NonNull<Node<T>>) -> Node<T> {
+        // SAFETY: guaranteed by the caller, see invariant 1.
+        *unsafe { Box::from_raw(node.as_ptr()) }
+    }
+}
+
+// What `merge_sort` returns when the comparator panics: every node of the
+// chain it was given, relinked in some order, and the panic to resume.
+struct Unwound<T> {
+    chain: Link<T>,
+    payload: Box<dyn Any + Send>,
+}
+
+// SAFETY: a `List<T>` owns its `T`s just like a `Vec<T>`; the raw pointers are
+// never shared with another list.
+unsafe impl<T: Send> Send for List<T> {}
+// SAFETY: `&List<T>` only hands out `&T`, and the finger is only read or
+// written under its mutex.
+unsafe impl<T: Sync> Sync for List<T> {}
+
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// `tail` is never copied: clones are rebuilt with `push_back` so their tail
+// points into their own nodes.
+impl<T: Clone> Clone for List<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for List<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for List<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
 }
 
-#[derive(PartialEq, Debug,Clone)]
-pub enum Node<T> {
-    Val { value: T, next: Box<Node<T>> },
-    Nil,
+/// Compares elements only; see the `Hash` impl for using `List` as a map key.
+impl<T: Eq> Eq for List<T> {}
+
+impl<T: PartialOrd> PartialOrd for List<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for List<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+/// # Caveat: `clippy::mutable_key_type`
+///
+/// The finger cache is an inline `Mutex`, so clippy's default-on
+/// `mutable_key_type` lint warns on `HashMap<List<_>, _>` and similar in any
+/// crate. The cache never changes the elements and `Hash`/`Eq` never read it,
+/// so such keys are sound. Silence the warning by adding
+/// `linkedlist_rust::linked_list::List` to `ignore-interior-mutability` in your
+/// `clippy.toml`, as this crate does for its own tests.
+impl<T: Hash> Hash for List<T> {
+    // Length-prefixed like slices, so nested lists hash unambiguously.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len);
+        for v in self {
+            v.hash(state);
+        }
+    }
+}
+
+impl<T> Drop for List<T> {
+    // Free one node at a time, so dropping never recurses.
+    fn drop(&mut self) {
+        while self.pop_front().is_some() {}
+    }
 }
 
-impl<T :Copy+PartialEq> List<T> {
+impl<T> List<T> {
     pub fn new() -> List<T> {
         List {
-            head: Box::new(Node::Nil),
-            tail: std::ptr::null_mut(),
+            head: None,
+            tail: None,
+            len: 0,
+            finger: Mutex::new(None),
+            marker: PhantomData,
         }
     }
 
-    fn empty() -> Box<Node<T>> {
-        Box::new(Node::Nil)
+    /// Returns the number of elements in O(1).
+    pub fn len(&self) -> usize {
+        self.len
     }
 
-    pub fn insert(&mut self, v: T) {
-        let old_head = std::mem::replace(&mut self.head, Self::empty());
-        let is_first = matches!(*old_head, Node::Nil);
-        let node = Node::Val {
-            value: v,
-            next: old_head,
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn out_of_range(&self, index: usize) -> ListError {
+        ListError::IndexOutOfRange { index, len: self.len }
+    }
+
+    // Returns the node at `index`, which the caller has already bounds-checked,
+    // and leaves the finger on it. The walk starts from the finger when it is
+    // at or before `index`, from `tail` for the last index, else from `head`.
+    fn node_at(&self, index: usize) -> NonNull<Node<T>> {
+        // Another thread holding the lock just means this lookup goes uncached.
+        let mut finger = self.finger.try_lock().ok();
+        let (mut i, mut node) = match finger.as_deref() {
+            _ if index + 1 == self.len => (index, self.tail.expect("index checked by caller")),
+            Some(&Some((i, node))) if i <= index => (i, node),
+            _ => (0, self.head.expect("index checked by caller")),
         };
-        *self.head = node;
-        if is_first {
-            self.tail = &mut *self.head as *mut Node<T>;
+
+        while i < index {
+            // SAFETY: `node` is linked into `self` (invariants 1 and 4).
+            node = unsafe { node.as_ref() }.next.expect("index checked by caller");
+            i += 1;
+        }
+
+        if let Some(finger) = finger.as_deref_mut() {
+            *finger = Some((index, node));
         }
+        node
     }
 
-    pub fn delete_nth(&mut self, index: usize) -> Result<T, &str> {
-        if index == 0 {
-            let Node::Val { value, next } = std::mem::replace(&mut *self.head, Node::Nil) else {
-                return Err("index out of range");
-            };
-            if matches!(*next, Node::Nil) {
-                self.tail = std::ptr::null_mut();
-            }
-            self.head = next;
-            return Ok(value);
+    // Clears the finger if it sits at or after `index`, where nodes are about
+    // to be linked or unlinked.
+    fn invalidate_finger(&mut self, index: usize) {
+        let finger = self.finger.get_mut().unwrap_or_else(|e| e.into_inner());
+        if matches!(*finger, Some((i, _)) if i >= index) {
+            *finger = None;
+        }
+    }
+
+    pub fn insert(&mut self, v: T) {
+        self.invalidate_finger(0);
+        let node = Node::alloc(v, self.head);
+        if self.tail.is_none() {
+            self.tail = Some(node);
         }
+        self.head = Some(node);
+        self.len += 1;
+    }
 
-        let mut current = &mut self.head;
-        let mut prev: Option<*mut Node<T>> = None;
+    /// Prepends `v`; same as [`List::insert`].
+    pub fn push_front(&mut self, v: T) {
+        self.insert(v);
+    }
 
-        for _ in 0..index {
-            prev = Some(&mut **current as *mut Node<T>);
-            let Node::Val { next, .. } = &mut **current else {
-                return Err("index out of range");
-            };
-            current = next;
+    /// Appends `v` after the last element in O(1) using the `tail` pointer.
+    pub fn push_back(&mut self, v: T) {
+        let node = Node::alloc(v, None);
+        match self.tail {
+            // SAFETY: `tail` is the last node of `self` (invariant 2) and we
+            // hold `&mut self`, so no reference to it is live.
+            Some(mut tail) => unsafe { tail.as_mut() }.next = Some(node),
+            None => self.head = Some(node),
         }
+        self.tail = Some(node);
+        self.len += 1;
+    }
 
-        let Node::Val { value, next } = std::mem::replace(&mut **current, Node::Nil) else {
-            return Err("index out of range");
-        };
+    /// Removes and returns the first element in O(1).
+    pub fn pop_front(&mut self) -> Option<T> {
+        let head = self.head?;
+        self.invalidate_finger(0);
+        // SAFETY: `head` is unlinked right below and never used again.
+        let node = unsafe { Node::free(head) };
+        self.head = node.next;
+        // Checked against `head` rather than the new `head` being `None`, so
+        // `tail` can never be left pointing at the freed node.
+        if self.tail == Some(head) {
+            self.tail = None;
+        }
+        self.len -= 1;
+        Some(node.value)
+    }
 
-        // Check if we're about to move the tail node
-        let moving_tail = !self.tail.is_null() && 
-            std::ptr::eq(self.tail, &*next as *const Node<T> as *mut Node<T>);
-        **current = *next;
+    /// Removes and returns the last element.
+    ///
+    /// The list is singly linked, so finding the new last node walks from
+    /// `head`: this is O(n), unlike [`List::pop_front`] and [`List::push_back`].
+    pub fn pop_back(&mut self) -> Option<T> {
+        let tail = self.tail?;
+        if self.head == Some(tail) {
+            return self.pop_front();
+        }
+        self.invalidate_finger(self.len - 1);
 
-        // Update tail pointer if the tail node was moved
-        if moving_tail {
-            self.tail = &mut **current as *mut Node<T>;
+        let mut prev = self.head?;
+        // SAFETY: every node visited is linked into `self` (invariant 1).
+        while unsafe { prev.as_ref() }.next != Some(tail) {
+            // SAFETY: as above; the walk stops at `tail`, so `prev` stays in `self`.
+            prev = unsafe { prev.as_ref() }.next?;
         }
 
-        if matches!(**current, Node::Nil) {
-            self.tail = prev.unwrap_or(std::ptr::null_mut());
+        // SAFETY: `prev` precedes `tail`; after this `tail` is unlinked and
+        // freed exactly once.
+        unsafe { prev.as_mut() }.next = None;
+        self.tail = Some(prev);
+        self.len -= 1;
+        // SAFETY: `tail` was unlinked just above and nothing points at it.
+        Some(unsafe { Node::free(tail) }.value)
+    }
+
+    pub fn delete_nth(&mut self, index: usize) -> Result<T, ListError> {
+        if index >= self.len {
+            return Err(self.out_of_range(index));
+        }
+        if index == 0 {
+            return Ok(self.pop_front().expect("index checked above"));
         }
 
-        Ok(value)
+        // The finger is left on `prev`, which keeps its index.
+        let mut prev = self.node_at(index - 1);
+        self.invalidate_finger(index);
+        // SAFETY: index < len, so `prev` has a successor; it is unlinked
+        // before being freed, and `tail` moves back if it was the removed node.
+        unsafe {
+            let target = prev.as_ref().next.expect("index checked above");
+            prev.as_mut().next = target.as_ref().next;
+            if self.tail == Some(target) {
+                self.tail = Some(prev);
+            }
+            self.len -= 1;
+            Ok(Node::free(target).value)
+        }
     }
 
-    pub fn insert_at(&mut self, v :T, index :usize) -> Result<(), &str> {
+    pub fn insert_at(&mut self, v :T, index :usize) -> Result<(), ListError> {
+        if index > self.len {
+            return Err(self.out_of_range(index));
+        }
         if index == 0 {
             self.insert(v);
             return Ok(());
         }
+        if index == self.len {
+            self.push_back(v);
+            return Ok(());
+        }
 
-        let mut current = &mut self.head;
+        let mut prev = self.node_at(index - 1);
+        self.invalidate_finger(index);
+        // SAFETY: `prev` is linked into `self`; it is not the tail since index < len.
+        unsafe {
+            let node = Node::alloc(v, prev.as_ref().next);
+            prev.as_mut().next = Some(node);
+        }
+        self.len += 1;
+        Ok(())
+    }
 
-        for _ in 0..index {
-            let Node::Val { value:_, next } = &mut **current else {
-                return Err("out of range");
-            };
+    /// Returns the element at `index`, or `None` if it is out of range.
+    ///
+    /// Consecutive lookups at the same or increasing indices continue from the
+    /// previous one, so reading `0..len` in order is O(n) overall.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len {
+            return None;
+        }
+        // SAFETY: the node belongs to `self`, borrowed shared for the result's lifetime.
+        Some(unsafe { &(*self.node_at(index).as_ptr()).value })
+    }
 
-            current = next;
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len {
+            return None;
         }
+        // SAFETY: the node belongs to `self`, borrowed mutably for the result's lifetime.
+        Some(unsafe { &mut (*self.node_at(index).as_ptr()).value })
+    }
+
+    pub fn first(&self) -> Option<&T> {
+        self.get_head()
+    }
+
+    pub fn first_mut(&mut self) -> Option<&mut T> {
+        self.get_head_mut()
+    }
+
+    /// Returns the last element in O(1) through the `tail` pointer.
+    pub fn last(&self) -> Option<&T> {
+        self.get_tail()
+    }
 
-        let n1 = std::mem::replace(&mut *current, Box::new(Node::Nil));
-        let is_at_end = matches!(*n1, Node::Nil);
-        let node = Node::Val { value: v, next:n1};
-        **current = node;
+    pub fn last_mut(&mut self) -> Option<&mut T> {
+        self.get_tail_mut()
+    }
 
-        if is_at_end {
-            self.tail = &mut **current as *mut Node<T>;
+    /// Swaps the elements at `i` and `j` in a single walk.
+    pub fn swap(&mut self, i: usize, j: usize) -> Result<(), ListError> {
+        let (lo, hi) = if i <= j { (i, j) } else { (j, i) };
+        if hi >= self.len {
+            return Err(self.out_of_range(hi));
+        }
+        if lo == hi {
+            return Ok(());
         }
 
+        let mut iter = self.iter_mut();
+        let a = iter.nth(lo).expect("index checked above");
+        let b = iter.nth(hi - lo - 1).expect("index checked above");
+        std::mem::swap(a, b);
         Ok(())
     }
 
-    pub fn get_head(&self) -> Option<T> {
-        let Node::Val { value, next:_ } = *self.head else {
-            return None;
+    pub fn get_head(&self) -> Option<&T> {
+        // SAFETY: `head` is owned by `self`, borrowed shared for the result's lifetime.
+        self.head.map(|node| unsafe { &(*node.as_ptr()).value })
+    }
+
+    pub fn get_head_mut(&mut self) -> Option<&mut T> {
+        // SAFETY: `head` is owned by `self`, borrowed mutably for the result's lifetime.
+        self.head.map(|node| unsafe { &mut (*node.as_ptr()).value })
+    }
+
+    pub fn get_tail(&self) -> Option<&T> {
+        // SAFETY: as in `get_head`; `tail` is the last node of `self`.
+        self.tail.map(|node| unsafe { &(*node.as_ptr()).value })
+    }
+
+    pub fn get_tail_mut(&mut self) -> Option<&mut T> {
+        // SAFETY: as in `get_head_mut`; `tail` is the last node of `self`.
+        self.tail.map(|node| unsafe { &mut (*node.as_ptr()).value })
+    }
+
+    /// Moves all elements of `other` onto the end of this list in O(1),
+    /// leaving `other` empty.
+    pub fn append(&mut self, other: &mut List<T>) {
+        // `other` keeps living after its nodes move to `self` (invariant 4).
+        other.invalidate_finger(0);
+        let Some(other_head) = other.head.take() else {
+            return;
         };
-        Some(value.clone())
+        match self.tail {
+            // SAFETY: `tail` is the last node of `self`; the nodes of `other`
+            // change owner, and `other` is left empty (invariant 1).
+            Some(mut tail) => unsafe { tail.as_mut() }.next = Some(other_head),
+            None => self.head = Some(other_head),
+        }
+        self.tail = other.tail.take();
+        self.len += std::mem::take(&mut other.len);
     }
 
-    pub fn get_tail(&self) -> Option<T> {
-        unsafe {
-            if self.tail.is_null() {
-                return None;
+    /// Splits the list in two at `at`, returning the elements from `at` on.
+    /// This walks `at` nodes, so it is O(n).
+    pub fn split_off(&mut self, at: usize) -> Result<List<T>, ListError> {
+        if at > self.len {
+            return Err(self.out_of_range(at));
+        }
+        if at == 0 {
+            return Ok(std::mem::take(self));
+        }
+
+        let mut suffix = List::new();
+        if at < self.len {
+            let mut prev = self.node_at(at - 1);
+            self.invalidate_finger(at);
+            // SAFETY: `prev` is linked into `self`; the nodes after it move to `suffix`.
+            suffix.head = unsafe { prev.as_mut() }.next.take();
+            suffix.tail = self.tail;
+            suffix.len = self.len - at;
+            self.tail = Some(prev);
+            self.len = at;
+        }
+        Ok(suffix)
+    }
+
+    pub fn contains(&self, x: &T) -> bool where T: PartialEq {
+        self.iter().any(|v| v == x)
+    }
+
+    /// Returns the index of the first element matching `pred`.
+    pub fn position<P: FnMut(&T) -> bool>(&self, pred: P) -> Option<usize> {
+        self.iter().position(pred)
+    }
+
+    pub fn find<P: FnMut(&T) -> bool>(&self, mut pred: P) -> Option<&T> {
+        self.iter().find(|v| pred(v))
+    }
+
+    /// Removes and returns the first element equal to `x`, in one pass.
+    pub fn remove_first(&mut self, x: &T) -> Option<T> where T: PartialEq {
+        let mut cursor = self.cursor_front_mut();
+        while let Some(v) = cursor.current() {
+            if *v == *x {
+                return cursor.remove_current();
             }
-            let Node::Val { value, next:_ } = *self.tail else {
-                return None;
-            };
-            return Some(value)
+            cursor.move_next();
         }
+        None
     }
-}
 
-impl<T :Display> Display for List<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut node = &self.head;
-        let mut s = String::new();
+    /// Removes every element equal to `x` in one pass and returns how many
+    /// were removed.
+    pub fn remove_all(&mut self, x: &T) -> usize where T: PartialEq {
+        let len = self.len;
+        self.retain(|v| v != x);
+        len - self.len
+    }
 
-        while let Node::Val { ref value, ref next } = **node {
-            write!(&mut s, "{} -> ", value)?;
-            node = next;
+    /// Keeps only the elements for which `f` returns `true`, in one pass.
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        let mut cursor = self.cursor_front_mut();
+        while let Some(v) = cursor.current() {
+            if f(v) {
+                cursor.move_next();
+            } else {
+                cursor.remove_current();
+            }
         }
-        write!(f, "[{}]", s.as_str())
     }
-}
 
-impl<T: PartialEq> Index<usize> for List<T> {
-    type Output = T;
+    /// Returns an iterator that removes and yields the elements for which
+    /// `pred` returns `true`. Elements not yet visited when the iterator is
+    /// dropped stay in the list.
+    pub fn extract_if<F: FnMut(&mut T) -> bool>(&mut self, pred: F) -> ExtractIf<'_, T, F> {
+        ExtractIf { cursor: self.cursor_front_mut(), pred }
+    }
 
-    fn index(&self, index: usize) -> &Self::Output {
-        let mut node = &self.head;
+    /// Removes consecutive repeated elements.
+    pub fn dedup(&mut self) where T: PartialEq {
+        self.dedup_by(|a, b| a == b);
+    }
+
+    pub fn dedup_by_key<K: PartialEq, F: FnMut(&mut T) -> K>(&mut self, mut key: F) {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
 
-        for _ in 0..index {
-            let Node::Val { ref next, .. } = **node else {
-                panic!("index out of bound");
+    /// Removes consecutive elements for which `same_bucket(current, previous)`
+    /// returns `true`, keeping the first of each run.
+    pub fn dedup_by<F: FnMut(&mut T, &mut T) -> bool>(&mut self, mut same_bucket: F) {
+        let mut cursor = self.cursor_front_mut();
+        cursor.move_next();
+
+        while let (Some(current), Some(prev)) = (cursor.current, cursor.prev) {
+            // SAFETY: `prev` is the last kept node and `current` the node after
+            // it, so the two references are to distinct nodes of the list the
+            // cursor borrows, and both end before the cursor moves.
+            let same = unsafe {
+                same_bucket(&mut (*current.as_ptr()).value, &mut (*prev.as_ptr()).value)
             };
+            if same {
+                cursor.remove_current();
+            } else {
+                cursor.move_next();
+            }
+        }
+    }
 
-            node = next;
+    /// Reverses the list in place in O(n) by relinking its nodes.
+    pub fn reverse(&mut self) {
+        if self.len < 2 {
+            return;
         }
+        self.invalidate_finger(0);
 
-        if let Node::Val { ref value, .. } = **node {
-            return value;
+        let old_head = self.head;
+        // SAFETY: the chain from `head` holds exactly `len` nodes of `self`.
+        let (new_head, _) = unsafe { Self::reverse_run(self.head, self.len) };
+        self.head = new_head;
+        self.tail = old_head;
+    }
+
+    /// Rotates the list in place so that the element at `k % len` becomes
+    /// the first. O(n), without allocating.
+    pub fn rotate_left(&mut self, k: usize) {
+        if self.len < 2 || k.is_multiple_of(self.len) {
+            return;
         }
+        let k = k % self.len;
 
-        panic!("index out of bound");
+        let mut new_tail = self.node_at(k - 1);
+        self.invalidate_finger(0);
+        // SAFETY: `new_tail` and `tail` are distinct nodes of `self`, since
+        // k - 1 < len - 1; the chain is closed into a ring and cut after `new_tail`.
+        unsafe {
+            self.tail.expect("list is not empty").as_mut().next = self.head;
+            self.head = new_tail.as_mut().next.take();
+        }
+        self.tail = Some(new_tail);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Rotates the list in place so that the last `k % len` elements move to
+    /// the front. O(n), without allocating.
+    pub fn rotate_right(&mut self, k: usize) {
+        if self.len < 2 {
+            return;
+        }
+        self.rotate_left(self.len - k % self.len);
+    }
 
-    #[test]
-    fn test_new() {
-        let list: List<i32> = List::new();
-        // A new list should be Nil
-        assert_eq!(*list.head, Node::Nil);
+    /// Reverses each consecutive group of `k` elements in place. A trailing
+    /// group shorter than `k` keeps its order. O(n), without allocating.
+    pub fn reverse_k_groups(&mut self, k: usize) {
+        if k < 2 || self.len < k {
+            return;
+        }
+        self.invalidate_finger(0);
+
+        let mut prev_group_last: Link<T> = None;
+        let mut group_first = self.head;
+        let mut remaining = self.len;
+
+        while remaining >= k {
+            // SAFETY: `remaining` nodes of `self` follow from `group_first`;
+            // after the reversal `group_first` is the last node of its group
+            // and is linked to the first node of the next group.
+            let rest = unsafe {
+                let (group_head, rest) = Self::reverse_run(group_first, k);
+                match prev_group_last {
+                    Some(mut last) => last.as_mut().next = group_head,
+                    None => self.head = group_head,
+                }
+                group_first.expect("group has k nodes").as_mut().next = rest;
+                rest
+            };
+            prev_group_last = group_first;
+            group_first = rest;
+            remaining -= k;
+        }
+
+        if remaining == 0 {
+            self.tail = prev_group_last;
+        }
     }
 
-    #[test]
-    #[should_panic(expected = "index out of bound")]
-    fn test_index_on_empty_list() {
-        let list: List<i32> = List::new();
-        let _ = list[0]; // should panic
+    /// Reverses the first `n` nodes of the chain starting at `start` and
+    /// returns the new first node and the node that followed the run. The old
+    /// first node is left with `next == None`.
+    ///
+    /// # Safety
+    ///
+    /// `start` must begin a chain of at least `n` nodes owned by one list.
+    unsafe fn reverse_run(start: Link<T>, n: usize) -> (Link<T>, Link<T>) {
+        let mut prev: Link<T> = None;
+        let mut current = start;
+        for _ in 0..n {
+            let mut node = current.expect("chain has n nodes");
+            // SAFETY: guaranteed by the caller; no reference to `node` is live.
+            let node_ref = unsafe { node.as_mut() };
+            current = node_ref.next;
+            node_ref.next = prev;
+            prev = Some(node);
+        }
+        (prev, current)
     }
 
-    #[test]
-    fn test_insert_and_index() {
-        let mut list = List::new();
-        list.insert(10);
-        list.insert(11);
-        list.insert(12);
+    /// Sorts the list with a stable merge sort in O(n log n).
+    pub fn sort(&mut self) where T: Ord {
+        self.sort_by(|a, b| a.cmp(b));
+    }
 
-        assert_eq!(list[0], 12);
-        assert_eq!(list[1], 11);
-        assert_eq!(list[2], 10);
+    pub fn sort_by_key<K: Ord, F: FnMut(&T) -> K>(&mut self, mut f: F) {
+        self.sort_by(|a, b| f(a).cmp(&f(b)));
     }
 
-    #[test]
-    #[should_panic(expected = "index out of bound")]
-    fn test_index_out_of_bounds() {
-        let mut list = List::new();
-        list.insert(10);
-        list.insert(11);
+    /// Sorts the list with a stable merge sort in O(n log n), ordering
+    /// elements by `compare`.
+    ///
+    /// The existing nodes are relinked, never reallocated.
+    pub fn sort_by<F: FnMut(&T, &T) -> Ordering>(&mut self, mut compare: F) {
+        let len = self.len;
+        if len < 2 {
+            return;
+        }
+        self.invalidate_finger(0);
 
-        let _ = list[2]; // Index 2 is out of bounds for a list with 2 elements.
+        // SAFETY: the chain from `head` holds exactly `len` nodes of `self`,
+        // and `tail` is recomputed once they have been relinked.
+        let (head, panic) = match unsafe { Self::merge_sort(self.head, len, &mut compare) } {
+            Ok(head) => (head, None),
+            Err(Unwound { chain, payload }) => (chain, Some(payload)),
+        };
+        self.head = head;
+        self.reset_tail();
+        if let Some(payload) = panic {
+            std::panic::resume_unwind(payload);
+        }
     }
 
-    #[test]
-    fn test_insert_at_beginning() {
-        let mut list = List::new();
-        list.insert(10);
-        list.insert(20);
+    /// # Safety
+    ///
+    /// `head` must start a `None`-terminated chain of exactly `len` nodes
+    /// owned by one list. On success the same nodes come back sorted; if
+    /// `compare` panics they come back as one unsorted chain.
+    unsafe fn merge_sort<F>(head: Link<T>, len: usize, compare: &mut F) -> Result<Link<T>, Unwound<T>>
+    where F: FnMut(&T, &T) -> Ordering {
+        if len < 2 {
+            return Ok(head);
+        }
 
-        assert!(list.insert_at(5, 0).is_ok());
-        assert_eq!(list[0], 5);
-        assert_eq!(list[1], 20);
-        assert_eq!(list[2], 10);
+        let mid = len / 2;
+        let mut last_left = head.expect("chain has len nodes");
+        for _ in 1..mid {
+            // SAFETY: the chain has `len` > `mid` nodes.
+            last_left = unsafe { last_left.as_ref() }.next.expect("chain has len nodes");
+        }
+        // SAFETY: cutting the chain in two; each half is sorted on its own.
+        let right = unsafe { last_left.as_mut() }.next.take();
+
+        // SAFETY: `head` now starts a `None`-terminated chain of exactly `mid`
+        // nodes. Until it returns, the other `len - mid` are held only by
+        // `right`, so a panic must hand them back along with the left half.
+        let left = match unsafe { Self::merge_sort(head, mid, compare) } {
+            Ok(left) => left,
+            Err(Unwound { chain, payload }) => {
+                // SAFETY: `chain` is the left half's nodes, disjoint from `right`.
+                return Err(Unwound { chain: unsafe { Self::concat(chain, right) }, payload });
+            },
+        };
+        // SAFETY: `right` starts the remaining `len - mid` nodes, disjoint from
+        // the sorted `left`, which is likewise handed back on a panic.
+        let right = match unsafe { Self::merge_sort(right, len - mid, compare) } {
+            Ok(right) => right,
+            Err(Unwound { chain, payload }) => {
+                // SAFETY: `left` and `chain` are the two disjoint halves.
+                return Err(Unwound { chain: unsafe { Self::concat(left, chain) }, payload });
+            },
+        };
+        // SAFETY: both halves are sorted, disjoint and `None`-terminated, and
+        // together hold all `len` nodes; `merge` relinks them into one chain
+        // whether or not `compare` panics.
+        unsafe { Self::merge(left, right, compare) }
     }
 
-    #[test]
-    fn test_insert_at_middle() {
-        let mut list = List::new();
-        list.insert(10);
-        list.insert(20);
-        list.insert(30);
+    /// # Safety
+    ///
+    /// `left` and `right` must be disjoint `None`-terminated chains owned by
+    /// one list.
+    unsafe fn merge<F>(mut left: Link<T>, mut right: Link<T>, compare: &mut F) -> Result<Link<T>, Unwound<T>>
+    where F: FnMut(&T, &T) -> Ordering {
+        let mut head: Link<T> = None;
+        let mut slot = &mut head;
 
-        assert!(list.insert_at(25, 1).is_ok());
-        assert_eq!(list[0], 30);
-        assert_eq!(list[1], 25);
-        assert_eq!(list[2], 20);
-        assert_eq!(list[3], 10);
+        loop {
+            let (Some(l), Some(r)) = (left, right) else {
+                *slot = left.or(right);
+                return Ok(head);
+            };
+
+            // SAFETY: `l` and `r` are distinct nodes; only shared references
+            // are held while comparing, and `slot` is either the local `head`
+            // or the `next` field of a node already moved into the result.
+            unsafe {
+                let ordering = panic::catch_unwind(AssertUnwindSafe(|| {
+                    compare(&r.as_ref().value, &l.as_ref().value)
+                }));
+                let Ok(ordering) = ordering else {
+                    // Hand every node back in one chain before unwinding further.
+                    *slot = Self::concat(left, right);
+                    return Err(Unwound { chain: head, payload: ordering.unwrap_err() });
+                };
+
+                // left wins ties, which keeps the sort stable
+                let mut node = if ordering == Ordering::Less {
+                    right = r.as_ref().next;
+                    r
+                } else {
+                    left = l.as_ref().next;
+                    l
+                };
+                *slot = Some(node);
+                slot = &mut node.as_mut().next;
+            }
+        }
     }
 
-    #[test]
-    fn test_insert_at_end() {
-        let mut list = List::new();
-        list.insert(10);
-        list.insert(20);
+    /// # Safety
+    ///
+    /// `front` and `back` must be disjoint `None`-terminated chains owned by
+    /// one list.
+    unsafe fn concat(front: Link<T>, back: Link<T>) -> Link<T> {
+        let Some(mut last) = front else {
+            return back;
+        };
+        // SAFETY: every node visited belongs to `front`, guaranteed by the caller.
+        unsafe {
+            while let Some(next) = last.as_ref().next {
+                last = next;
+            }
+            last.as_mut().next = back;
+        }
+        front
+    }
 
-        assert!(list.insert_at(5, 2).is_ok());
-        assert_eq!(list[0], 20);
-        assert_eq!(list[1], 10);
-        assert_eq!(list[2], 5);
+    // Points `tail` at the last node again after the chain has been relinked.
+    fn reset_tail(&mut self) {
+        let mut tail = self.head;
+        while let Some(node) = tail {
+            // SAFETY: every node visited is linked into `self` (invariant 1).
+            let next = unsafe { node.as_ref() }.next;
+            if next.is_none() {
+                break;
+            }
+            tail = next;
+        }
+        self.tail = tail;
     }
 
-    #[test]
-    fn test_insert_at_empty_list() {
-        let mut list: List<i32> = List::new();
+    /// Returns a formatting builder; `{}` on the list itself uses its defaults,
+    /// `[a -> b -> c]`.
+    pub fn display(&self) -> ListDisplay<'_, T> {
+        ListDisplay {
+            list: self,
+            separator: " -> ",
+            open: "[",
+            close: "]",
+            max_items: None,
+        }
+    }
 
-        assert!(list.insert_at(42, 0).is_ok());
-        assert_eq!(list[0], 42);
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { node: self.head, marker: PhantomData }
     }
 
-    #[test]
-    fn test_insert_at_out_of_range() {
-        let mut list = List::new();
-        list.insert(10);
-        list.insert(20);
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut { node: self.head, marker: PhantomData }
+    }
 
-        let result = list.insert_at(99, 5);
-        assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), "out of range");
+    /// Returns a cursor positioned on the first element.
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        // Cursor edits can happen anywhere.
+        self.invalidate_finger(0);
+        CursorMut {
+            current: self.head,
+            prev: None,
+            list: self,
+        }
     }
+}
 
-    #[test]
-    fn test_insert_at_out_of_range_empty_list() {
-        let mut list: List<i32> = List::new();
+/// A cursor over a [`List`] that can edit the list at its position in O(1).
+///
+/// The cursor rests either on an element or, once it has been moved past the
+/// last element, at the end of the list.
+pub struct CursorMut<'a, T> {
+    list: &'a mut List<T>,
+    // `current` is `None` at the end; `prev` is the node before `current`,
+    // `None` when `current` is the head. Both are nodes of `list`.
+    current: Link<T>,
+    prev: Link<T>,
+}
 
-        let result = list.insert_at(42, 1);
-        assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), "out of range");
+impl<'a, T> CursorMut<'a, T> {
+    // Points whatever precedes the cursor (`prev` or the list head) at `node`.
+    fn link_prev(&mut self, node: Link<T>) {
+        match self.prev {
+            // SAFETY: `prev` is a node of the list the cursor borrows mutably.
+            Some(mut prev) => unsafe { prev.as_mut() }.next = node,
+            None => self.list.head = node,
+        }
+    }
+
+    /// Returns `true` if the cursor is past the last element.
+    pub fn is_end(&self) -> bool {
+        self.current.is_none()
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        // SAFETY: the node belongs to the list borrowed by the cursor, and the
+        // result borrows the cursor mutably.
+        self.current.map(|node| unsafe { &mut (*node.as_ptr()).value })
+    }
+
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        // SAFETY: as in `current`.
+        let next = unsafe { self.current?.as_ref() }.next?;
+        // SAFETY: `next` is the node after `current` in the same list; the
+        // result borrows the cursor mutably.
+        Some(unsafe { &mut (*next.as_ptr()).value })
+    }
+
+    /// Moves to the next element. Does nothing once past the last element.
+    pub fn move_next(&mut self) {
+        let Some(current) = self.current else {
+            return;
+        };
+        self.prev = Some(current);
+        // SAFETY: `current` is a node of the borrowed list.
+        self.current = unsafe { current.as_ref() }.next;
+    }
+
+    /// Inserts `v` before the current element; the cursor stays on that element.
+    /// Past the end, this appends `v`.
+    pub fn insert_before(&mut self, v: T) {
+        let node = Node::alloc(v, self.current);
+        self.link_prev(Some(node));
+        if self.current.is_none() {
+            self.list.tail = Some(node);
+        }
+        self.list.len += 1;
+        self.prev = Some(node);
+    }
+
+    /// Inserts `v` after the current element. Past the end, this appends `v`.
+    pub fn insert_after(&mut self, v: T) {
+        let Some(mut current) = self.current else {
+            self.insert_before(v);
+            return;
+        };
+
+        // SAFETY: `current` is a node of the borrowed list; no reference to it is live.
+        let current_ref = unsafe { current.as_mut() };
+        let node = Node::alloc(v, current_ref.next);
+        current_ref.next = Some(node);
+        if self.list.tail == Some(current) {
+            self.list.tail = Some(node);
+        }
+        self.list.len += 1;
+    }
+
+    /// Removes the current element and moves the cursor to the one after it.
+    pub fn remove_current(&mut self) -> Option<T> {
+        let current = self.current?;
+        // SAFETY: `current` is unlinked below before anything else can reach it.
+        let node = unsafe { Node::free(current) };
+        self.link_prev(node.next);
+        if self.list.tail == Some(current) {
+            self.list.tail = self.prev;
+        }
+        self.list.len -= 1;
+        self.current = node.next;
+        Some(node.value)
+    }
+
+    /// Moves all elements of `other` in before the current element; the cursor
+    /// stays on that element. Past the end, this appends `other`.
+    pub fn splice_before(&mut self, mut other: List<T>) {
+        let (Some(other_head), Some(mut other_tail)) = (other.head.take(), other.tail.take()) else {
+            return;
+        };
+
+        // SAFETY: the nodes of `other` change owner, and `other` is left empty.
+        unsafe { other_tail.as_mut() }.next = self.current;
+        self.link_prev(Some(other_head));
+        if self.current.is_none() {
+            self.list.tail = Some(other_tail);
+        }
+        self.list.len += std::mem::take(&mut other.len);
+        self.prev = Some(other_tail);
+    }
+
+    /// Moves all elements of `other` in after the current element. Past the
+    /// end, this appends `other`.
+    pub fn splice_after(&mut self, mut other: List<T>) {
+        let Some(mut current) = self.current else {
+            self.splice_before(other);
+            return;
+        };
+        let (Some(other_head), Some(mut other_tail)) = (other.head.take(), other.tail.take()) else {
+            return;
+        };
+
+        // SAFETY: `current` is a node of the borrowed list; the nodes of
+        // `other` change owner, and `other` is left empty.
+        unsafe {
+            other_tail.as_mut().next = current.as_ref().next;
+            current.as_mut().next = Some(other_head);
+        }
+        if self.list.tail == Some(current) {
+            self.list.tail = Some(other_tail);
+        }
+        self.list.len += std::mem::take(&mut other.len);
+    }
+}
+
+pub struct ExtractIf<'a, T, F: FnMut(&mut T) -> bool> {
+    cursor: CursorMut<'a, T>,
+    pred: F,
+}
+
+impl<T, F: FnMut(&mut T) -> bool> Iterator for ExtractIf<'_, T, F> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(v) = self.cursor.current() {
+            if (self.pred)(v) {
+                return self.cursor.remove_current();
+            }
+            self.cursor.move_next();
+        }
+        None
+    }
+}
+
+pub struct Iter<'a, T> {
+    node: Link<T>,
+    marker: PhantomData<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // SAFETY: the list is borrowed shared for 'a, so its nodes stay alive
+        // and unmodified.
+        let node = unsafe { &*self.node?.as_ptr() };
+        self.node = node.next;
+        Some(&node.value)
+    }
+}
+
+// SAFETY: `Iter` behaves like `&List<T>`.
+unsafe impl<T: Sync> Send for Iter<'_, T> {}
+unsafe impl<T: Sync> Sync for Iter<'_, T> {}
+
+pub struct IterMut<'a, T> {
+    node: Link<T>,
+    marker: PhantomData<&'a mut Node<T>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // SAFETY: the list is borrowed mutably for 'a and each node is yielded
+        // at most once, so the returned references never alias.
+        let node = unsafe { &mut *self.node?.as_ptr() };
+        self.node = node.next;
+        Some(&mut node.value)
+    }
+}
+
+// SAFETY: `IterMut` behaves like `&mut List<T>`.
+unsafe impl<T: Send> Send for IterMut<'_, T> {}
+unsafe impl<T: Sync> Sync for IterMut<'_, T> {}
+
+pub struct IntoIter<T>(List<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop_front()
+    }
+}
+
+impl<T> IntoIterator for List<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a List<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut List<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T> Extend<T> for List<T> {
+    /// Appends the items in iteration order after the current last element.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for v in iter {
+            self.push_back(v);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for List<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = List::new();
+        list.extend(iter);
+        list
+    }
+}
+
+/// Configurable formatting for a [`List`], created by [`List::display`].
+///
+/// Elements are written straight to the formatter, which is passed through to
+/// each element so width, precision and `{:#}` apply per element.
+pub struct ListDisplay<'a, T> {
+    list: &'a List<T>,
+    separator: &'a str,
+    open: &'a str,
+    close: &'a str,
+    max_items: Option<usize>,
+}
+
+impl<'a, T> ListDisplay<'a, T> {
+    pub fn separator(mut self, separator: &'a str) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    pub fn brackets(mut self, open: &'a str, close: &'a str) -> Self {
+        self.open = open;
+        self.close = close;
+        self
+    }
+
+    /// Prints at most `max_items` elements followed by "… N more".
+    pub fn max_items(mut self, max_items: usize) -> Self {
+        self.max_items = Some(max_items);
+        self
+    }
+}
+
+impl<T :Display> Display for ListDisplay<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.open)?;
+
+        // `len` is cached, so the elements past the limit are never walked.
+        let shown = self.max_items.map_or(self.list.len(), |max| max.min(self.list.len()));
+        for (i, value) in self.list.iter().take(shown).enumerate() {
+            if i > 0 {
+                f.write_str(self.separator)?;
+            }
+            value.fmt(f)?;
+        }
+
+        let rest = self.list.len() - shown;
+        if rest > 0 {
+            if shown > 0 {
+                f.write_str(self.separator)?;
+            }
+            write!(f, "… {} more", rest)?;
+        }
+
+        f.write_str(self.close)
+    }
+}
+
+impl<T :Display> Display for List<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.display().fmt(f)
+    }
+}
+
+impl<T> Index<usize> for List<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).expect("index out of bound")
+    }
+}
+
+impl<T> IndexMut<usize> for List<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.get_mut(index).expect("index out of bound")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let list: List<i32> = List::new();
+        // A new list has no nodes
+        assert!(list.head.is_none());
+        assert!(list.tail.is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bound")]
+    fn test_index_on_empty_list() {
+        let list: List<i32> = List::new();
+        let _ = list[0]; // should panic
+    }
+
+    #[test]
+    fn test_insert_and_index() {
+        let mut list = List::new();
+        list.insert(10);
+        list.insert(11);
+        list.insert(12);
+
+        assert_eq!(list[0], 12);
+        assert_eq!(list[1], 11);
+        assert_eq!(list[2], 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bound")]
+    fn test_index_out_of_bounds() {
+        let mut list = List::new();
+        list.insert(10);
+        list.insert(11);
+
+        let _ = list[2]; // Index 2 is out of bounds for a list with 2 elements.
+    }
+
+    #[test]
+    fn test_insert_at_beginning() {
+        let mut list = List::new();
+        list.insert(10);
+        list.insert(20);
+
+        assert!(list.insert_at(5, 0).is_ok());
+        assert_eq!(list[0], 5);
+        assert_eq!(list[1], 20);
+        assert_eq!(list[2], 10);
+    }
+
+    #[test]
+    fn test_insert_at_middle() {
+        let mut list = List::new();
+        list.insert(10);
+        list.insert(20);
+        list.insert(30);
+
+        assert!(list.insert_at(25, 1).is_ok());
+        assert_eq!(list[0], 30);
+        assert_eq!(list[1], 25);
+        assert_eq!(list[2], 20);
+        assert_eq!(list[3], 10);
+    }
+
+    #[test]
+    fn test_insert_at_end() {
+        let mut list = List::new();
+        list.insert(10);
+        list.insert(20);
+
+        assert!(list.insert_at(5, 2).is_ok());
+        assert_eq!(list[0], 20);
+        assert_eq!(list[1], 10);
+        assert_eq!(list[2], 5);
+    }
+
+    #[test]
+    fn test_insert_at_empty_list() {
+        let mut list: List<i32> = List::new();
+
+        assert!(list.insert_at(42, 0).is_ok());
+        assert_eq!(list[0], 42);
+    }
+
+    #[test]
+    fn test_insert_at_out_of_range() {
+        let mut list = List::new();
+        list.insert(10);
+        list.insert(20);
+
+        let result = list.insert_at(99, 5);
+        assert_eq!(result, Err(ListError::IndexOutOfRange { index: 5, len: 2 }));
+    }
+
+    #[test]
+    fn test_insert_at_out_of_range_empty_list() {
+        let mut list: List<i32> = List::new();
+
+        let result = list.insert_at(42, 1);
+        assert_eq!(result, Err(ListError::IndexOutOfRange { index: 1, len: 0 }));
+    }
+
+    #[test]
+    fn test_delete_nth_out_of_range() {
+        let mut list = List::new();
+        list.insert(10);
+
+        assert_eq!(list.delete_nth(1), Err(ListError::IndexOutOfRange { index: 1, len: 1 }));
+        assert_eq!(list.delete_nth(0), Ok(10));
+        assert_eq!(list.delete_nth(0), Err(ListError::IndexOutOfRange { index: 0, len: 0 }));
+    }
+
+    #[test]
+    fn test_error_display() {
+        let err = ListError::IndexOutOfRange { index: 3, len: 2 };
+        assert_eq!(err.to_string(), "index 3 out of range for list of length 2");
+    }
+
+    #[test]
+    fn test_get_head_and_tail() {
+        let mut list: List<i32> = List::new();
+        assert_eq!(list.get_head(), None);
+        assert_eq!(list.get_tail(), None);
+
+        list.insert(10);
+        list.insert(20);
+        assert_eq!(list.get_head(), Some(&20));
+        assert_eq!(list.get_tail(), Some(&10));
+
+        *list.get_head_mut().unwrap() += 1;
+        *list.get_tail_mut().unwrap() += 2;
+        assert_eq!(list[0], 21);
+        assert_eq!(list[1], 12);
+    }
+
+    #[test]
+    fn test_non_copy_values() {
+        let mut list: List<String> = List::new();
+        list.insert(String::from("b"));
+        list.insert(String::from("a"));
+        list.insert_at(String::from("c"), 2).unwrap();
+
+        assert_eq!(list.get_head().map(String::as_str), Some("a"));
+        assert_eq!(list.get_tail().map(String::as_str), Some("c"));
+        assert_eq!(list.delete_nth(1).unwrap(), "b");
+        assert_eq!(list.to_string(), "[a -> c]");
+    }
+
+    #[test]
+    fn test_iter() {
+        let mut list = List::new();
+        list.insert(10);
+        list.insert(20);
+        list.insert(30);
+
+        let values: Vec<&i32> = list.iter().collect();
+        assert_eq!(values, vec![&30, &20, &10]);
+        assert_eq!(List::<i32>::new().iter().next(), None);
+    }
+
+    #[test]
+    fn test_iter_mut() {
+        let mut list: List<i32> = (1..=3).collect();
+        for v in &mut list {
+            *v *= 10;
+        }
+
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![10, 20, 30]);
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let list: List<String> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+        let values: Vec<String> = list.into_iter().collect();
+        assert_eq!(values, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_from_iter_keeps_order() {
+        let list: List<i32> = vec![1, 2, 3].into_iter().collect();
+        assert_eq!(list[0], 1);
+        assert_eq!(list[2], 3);
+        assert_eq!(list.get_tail(), Some(&3));
+
+        let empty: List<i32> = std::iter::empty().collect();
+        assert_eq!(empty.get_tail(), None);
+    }
+
+    #[test]
+    fn test_extend() {
+        let mut list: List<i32> = List::new();
+        list.insert(1);
+        list.extend(vec![2, 3]);
+        list.extend(std::iter::empty());
+        list.insert_at(4, 3).unwrap();
+
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert_eq!(list.get_tail(), Some(&4));
+    }
+
+    #[test]
+    fn test_for_loop_over_ref() {
+        let list: List<i32> = (1..=4).collect();
+        let mut sum = 0;
+        for v in &list {
+            sum += v;
+        }
+        assert_eq!(sum, 10);
+    }
+
+    #[test]
+    fn test_push_back() {
+        let mut list = List::new();
+        list.push_back(1);
+        list.push_back(2);
+        list.push_front(0);
+        list.push_back(3);
+
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+        assert_eq!(list.get_head(), Some(&0));
+        assert_eq!(list.get_tail(), Some(&3));
+    }
+
+    #[test]
+    fn test_pop_front() {
+        let mut list: List<i32> = (1..=2).collect();
+
+        assert_eq!(list.pop_front(), Some(1));
+        assert_eq!(list.pop_front(), Some(2));
+        assert_eq!(list.pop_front(), None);
+        assert_eq!(list.get_tail(), None);
+
+        list.push_back(3);
+        assert_eq!(list.get_head(), Some(&3));
+        assert_eq!(list.get_tail(), Some(&3));
+    }
+
+    #[test]
+    fn test_pop_back() {
+        let mut list: List<i32> = (1..=3).collect();
+
+        assert_eq!(list.pop_back(), Some(3));
+        assert_eq!(list.get_tail(), Some(&2));
+        list.push_back(4);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 4]);
+
+        assert_eq!(list.pop_back(), Some(4));
+        assert_eq!(list.pop_back(), Some(2));
+        assert_eq!(list.pop_back(), Some(1));
+        assert_eq!(list.pop_back(), None);
+        assert_eq!(list.get_head(), None);
+        assert_eq!(list.get_tail(), None);
+    }
+
+    #[test]
+    fn test_queue_usage() {
+        let mut queue = List::new();
+        for i in 0..5 {
+            queue.push_back(i);
+        }
+        let mut out = Vec::new();
+        while let Some(v) = queue.pop_front() {
+            out.push(v);
+        }
+        assert_eq!(out, vec![0, 1, 2, 3, 4]);
+    }
+
+    fn to_vec(list: &List<i32>) -> Vec<i32> {
+        list.iter().copied().collect()
+    }
+
+    fn to_vec_str(list: &List<String>) -> Vec<&str> {
+        list.iter().map(String::as_str).collect()
+    }
+
+    #[test]
+    fn test_cursor_move_and_current() {
+        let mut list: List<i32> = (1..=3).collect();
+        let mut cursor = list.cursor_front_mut();
+
+        assert_eq!(cursor.current(), Some(&mut 1));
+        assert_eq!(cursor.peek_next(), Some(&mut 2));
+        cursor.move_next();
+        *cursor.current().unwrap() = 20;
+        cursor.move_next();
+        cursor.move_next();
+        assert!(cursor.is_end());
+        assert_eq!(cursor.current(), None);
+        cursor.move_next();
+        assert!(cursor.is_end());
+
+        assert_eq!(to_vec(&list), vec![1, 20, 3]);
+    }
+
+    #[test]
+    fn test_cursor_insert_before_and_after() {
+        let mut list: List<i32> = vec![2, 4].into_iter().collect();
+        let mut cursor = list.cursor_front_mut();
+
+        cursor.insert_before(1);
+        assert_eq!(cursor.current(), Some(&mut 2));
+        cursor.insert_after(3);
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 4));
+        cursor.insert_after(5);
+        cursor.move_next();
+        cursor.move_next();
+        cursor.insert_before(6);
+        assert!(cursor.is_end());
+        cursor.insert_after(7);
+
+        assert_eq!(to_vec(&list), vec![1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(list.get_tail(), Some(&7));
+        list.push_back(8);
+        assert_eq!(list.get_tail(), Some(&8));
+    }
+
+    #[test]
+    fn test_cursor_insert_on_empty_list() {
+        let mut list: List<i32> = List::new();
+        let mut cursor = list.cursor_front_mut();
+        cursor.insert_before(1);
+        cursor.insert_after(2);
+
+        assert_eq!(to_vec(&list), vec![1, 2]);
+        assert_eq!(list.get_tail(), Some(&2));
+    }
+
+    #[test]
+    fn test_cursor_insert_before_tail() {
+        let mut list: List<i32> = vec![1].into_iter().collect();
+        let mut cursor = list.cursor_front_mut();
+        cursor.insert_before(0);
+        assert_eq!(cursor.current(), Some(&mut 1));
+
+        assert_eq!(list.get_tail(), Some(&1));
+        list.push_back(2);
+        assert_eq!(to_vec(&list), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_cursor_remove_current() {
+        let mut list: List<i32> = (1..=4).collect();
+        let mut cursor = list.cursor_front_mut();
+
+        assert_eq!(cursor.remove_current(), Some(1));
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some(3));
+        assert_eq!(cursor.current(), Some(&mut 4));
+        assert_eq!(list.get_tail(), Some(&4));
+        list.push_back(5);
+        assert_eq!(to_vec(&list), vec![2, 4, 5]);
+
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some(5));
+        assert!(cursor.is_end());
+        assert_eq!(cursor.remove_current(), None);
+        assert_eq!(list.get_tail(), Some(&4));
+
+        let mut cursor = list.cursor_front_mut();
+        cursor.remove_current();
+        cursor.remove_current();
+        assert_eq!(list.get_tail(), None);
+        list.push_back(6);
+        assert_eq!(to_vec(&list), vec![6]);
+    }
+
+    #[test]
+    fn test_cursor_splice() {
+        let mut list: List<i32> = vec![1, 5].into_iter().collect();
+        let mut cursor = list.cursor_front_mut();
+
+        cursor.splice_after((2..=3).collect());
+        cursor.move_next();
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 5));
+        cursor.splice_before(vec![4].into_iter().collect());
+        assert_eq!(cursor.current(), Some(&mut 5));
+        cursor.splice_after((6..=7).collect());
+        cursor.splice_after(List::new());
+        cursor.splice_before(List::new());
+
+        assert_eq!(to_vec(&list), vec![1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(list.get_tail(), Some(&7));
+
+        let mut cursor = list.cursor_front_mut();
+        for _ in 0..7 {
+            cursor.move_next();
+        }
+        cursor.splice_before(vec![8].into_iter().collect());
+        cursor.splice_after((9..=10).collect());
+        assert_eq!(list.get_tail(), Some(&10));
+        list.push_back(11);
+        assert_eq!(to_vec(&list), (1..=11).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_cursor_splice_into_empty_list() {
+        let mut list: List<i32> = List::new();
+        list.cursor_front_mut().splice_before((1..=2).collect());
+        assert_eq!(list.get_tail(), Some(&2));
+
+        let mut other: List<i32> = List::new();
+        other.cursor_front_mut().splice_after(vec![1].into_iter().collect());
+        assert_eq!(other.get_tail(), Some(&1));
+        other.push_back(2);
+        assert_eq!(to_vec(&other), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_drop_long_list() {
+        let n = if cfg!(miri) { 1_000 } else { 2_000_000 };
+        let list: List<u32> = (0..n).collect();
+        drop(list);
+    }
+
+    #[test]
+    fn test_sort() {
+        let mut list: List<i32> = vec![5, 1, 4, 2, 3, 2].into_iter().collect();
+        list.sort();
+
+        assert_eq!(to_vec(&list), vec![1, 2, 2, 3, 4, 5]);
+        assert_eq!(list.get_tail(), Some(&5));
+        list.push_back(6);
+        assert_eq!(list.get_tail(), Some(&6));
+    }
+
+    #[test]
+    fn test_sort_small_lists() {
+        let mut empty: List<i32> = List::new();
+        empty.sort();
+        assert_eq!(empty.get_tail(), None);
+
+        let mut single: List<i32> = vec![1].into_iter().collect();
+        single.sort();
+        assert_eq!(to_vec(&single), vec![1]);
+        assert_eq!(single.get_tail(), Some(&1));
+
+        let mut pair: List<i32> = vec![2, 1].into_iter().collect();
+        pair.sort();
+        assert_eq!(to_vec(&pair), vec![1, 2]);
+        assert_eq!(pair.get_tail(), Some(&2));
+    }
+
+    #[test]
+    fn test_sort_by_is_stable() {
+        let pairs = vec![(3, 'a'), (1, 'b'), (3, 'c'), (2, 'd'), (1, 'e'), (3, 'f'), (2, 'g')];
+        let mut list: List<(i32, char)> = pairs.iter().copied().collect();
+        list.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut expected = pairs;
+        expected.sort_by_key(|a| a.0);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn test_sort_by_key() {
+        let mut list: List<String> = ["ccc", "a", "bb", "dd"].iter().map(|s| s.to_string()).collect();
+        list.sort_by_key(|s| s.len());
+
+        assert_eq!(list.iter().map(String::as_str).collect::<Vec<_>>(), vec!["a", "bb", "dd", "ccc"]);
+        assert_eq!(list.get_tail().map(String::as_str), Some("ccc"));
+    }
+
+    #[test]
+    fn test_sort_keeps_nodes() {
+        let mut list: List<i32> = (0..100).rev().collect();
+        let before: Vec<*const i32> = list.iter().map(|v| v as *const i32).collect();
+        list.sort();
+
+        let after: Vec<*const i32> = list.iter().map(|v| v as *const i32).collect();
+        assert_eq!(to_vec(&list), (0..100).collect::<Vec<_>>());
+        assert!(after.iter().all(|p| before.contains(p)));
+    }
+
+    #[test]
+    fn test_sort_matches_vec() {
+        let mut seed = 12345u32;
+        let values: Vec<u32> = (0..if cfg!(miri) { 200 } else { 1000 }).map(|_| {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            (seed >> 16) % 100
+        }).collect();
+
+        let mut list: List<u32> = values.iter().copied().collect();
+        list.sort();
+        let mut expected = values;
+        expected.sort();
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn test_append() {
+        let mut list: List<i32> = (1..=2).collect();
+        let mut other: List<i32> = (3..=4).collect();
+        list.append(&mut other);
+
+        assert_eq!(to_vec(&list), vec![1, 2, 3, 4]);
+        assert_eq!(list.get_tail(), Some(&4));
+        assert_eq!(other.get_head(), None);
+        assert_eq!(other.get_tail(), None);
+
+        list.push_back(5);
+        other.push_back(6);
+        assert_eq!(to_vec(&list), vec![1, 2, 3, 4, 5]);
+        assert_eq!(to_vec(&other), vec![6]);
+    }
+
+    #[test]
+    fn test_append_empty() {
+        let mut list: List<i32> = List::new();
+        let mut other: List<i32> = (1..=2).collect();
+        list.append(&mut other);
+        assert_eq!(to_vec(&list), vec![1, 2]);
+        assert_eq!(list.get_tail(), Some(&2));
+        assert_eq!(other.get_tail(), None);
+
+        list.append(&mut other);
+        assert_eq!(to_vec(&list), vec![1, 2]);
+        assert_eq!(list.get_tail(), Some(&2));
+    }
+
+    #[test]
+    fn test_split_off() {
+        let mut list: List<i32> = (1..=5).collect();
+        let mut suffix = list.split_off(2).unwrap();
+
+        assert_eq!(to_vec(&list), vec![1, 2]);
+        assert_eq!(to_vec(&suffix), vec![3, 4, 5]);
+        assert_eq!(list.get_tail(), Some(&2));
+        assert_eq!(suffix.get_tail(), Some(&5));
+
+        list.push_back(6);
+        suffix.push_back(7);
+        assert_eq!(to_vec(&list), vec![1, 2, 6]);
+        assert_eq!(to_vec(&suffix), vec![3, 4, 5, 7]);
+    }
+
+    #[test]
+    fn test_split_off_edges() {
+        let mut list: List<i32> = (1..=3).collect();
+
+        let end = list.split_off(3).unwrap();
+        assert_eq!(end.get_head(), None);
+        assert_eq!(list.get_tail(), Some(&3));
+
+        let all = list.split_off(0).unwrap();
+        assert_eq!(to_vec(&all), vec![1, 2, 3]);
+        assert_eq!(all.get_tail(), Some(&3));
+        assert_eq!(list.get_head(), None);
+        assert_eq!(list.get_tail(), None);
+
+        assert_eq!(list.split_off(1).err(), Some(ListError::IndexOutOfRange { index: 1, len: 0 }));
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut list: List<i32> = (1..=6).collect();
+        list.retain(|v| v % 2 == 0);
+
+        assert_eq!(to_vec(&list), vec![2, 4, 6]);
+        assert_eq!(list.get_tail(), Some(&6));
+
+        list.retain(|v| *v < 5);
+        assert_eq!(list.get_tail(), Some(&4));
+        list.push_back(8);
+        assert_eq!(to_vec(&list), vec![2, 4, 8]);
+
+        list.retain(|_| false);
+        assert_eq!(list.get_head(), None);
+        assert_eq!(list.get_tail(), None);
+    }
+
+    #[test]
+    fn test_extract_if() {
+        let mut list: List<i32> = (1..=6).collect();
+        let evens: Vec<i32> = list.extract_if(|v| *v % 2 == 0).collect();
+
+        assert_eq!(evens, vec![2, 4, 6]);
+        assert_eq!(to_vec(&list), vec![1, 3, 5]);
+        assert_eq!(list.get_tail(), Some(&5));
+        list.push_back(7);
+        assert_eq!(to_vec(&list), vec![1, 3, 5, 7]);
+    }
+
+    #[test]
+    fn test_extract_if_partial() {
+        let mut list: List<i32> = (1..=6).collect();
+        assert_eq!(list.extract_if(|v| *v > 2).next(), Some(3));
+
+        assert_eq!(to_vec(&list), vec![1, 2, 4, 5, 6]);
+        assert_eq!(list.get_tail(), Some(&6));
+    }
+
+    #[test]
+    fn test_dedup() {
+        let mut list: List<i32> = vec![1, 1, 2, 3, 3, 3, 1, 4, 4].into_iter().collect();
+        list.dedup();
+
+        assert_eq!(to_vec(&list), vec![1, 2, 3, 1, 4]);
+        assert_eq!(list.get_tail(), Some(&4));
+        list.push_back(5);
+        assert_eq!(list.get_tail(), Some(&5));
+
+        let mut empty: List<i32> = List::new();
+        empty.dedup();
+        assert_eq!(empty.get_tail(), None);
+    }
+
+    #[test]
+    fn test_dedup_by_key() {
+        let mut list: List<i32> = vec![10, 11, 20, 21, 22, 30].into_iter().collect();
+        list.dedup_by_key(|v| *v / 10);
+        assert_eq!(to_vec(&list), vec![10, 20, 30]);
+
+        let mut words: List<String> = ["a", "A", "b", "B", "b"].iter().map(|s| s.to_string()).collect();
+        words.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+        assert_eq!(words.iter().map(String::as_str).collect::<Vec<_>>(), vec!["a", "b"]);
+        assert_eq!(words.get_tail().map(String::as_str), Some("b"));
+    }
+
+    #[test]
+    fn test_get() {
+        let list: List<i32> = (1..=3).collect();
+
+        assert_eq!(list.get(0), Some(&1));
+        assert_eq!(list.get(2), Some(&3));
+        assert_eq!(list.get(3), None);
+        assert_eq!(List::<i32>::new().get(0), None);
+    }
+
+    #[test]
+    fn test_get_mut_and_index_mut() {
+        let mut list: List<i32> = (1..=3).collect();
+
+        *list.get_mut(1).unwrap() = 20;
+        list[2] += 28;
+        assert_eq!(list.get_mut(3), None);
+        assert_eq!(to_vec(&list), vec![1, 20, 31]);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bound")]
+    fn test_index_mut_out_of_bounds() {
+        let mut list: List<i32> = (1..=2).collect();
+        list[2] = 0;
+    }
+
+    #[test]
+    fn test_first_and_last() {
+        let mut list: List<i32> = List::new();
+        assert_eq!(list.first(), None);
+        assert_eq!(list.last(), None);
+
+        list.extend(1..=3);
+        assert_eq!(list.first(), Some(&1));
+        assert_eq!(list.last(), Some(&3));
+
+        *list.first_mut().unwrap() = 0;
+        *list.last_mut().unwrap() = 4;
+        assert_eq!(to_vec(&list), vec![0, 2, 4]);
+    }
+
+    #[test]
+    fn test_swap() {
+        let mut list: List<i32> = (1..=4).collect();
+
+        assert_eq!(list.swap(0, 3), Ok(()));
+        assert_eq!(list.swap(2, 1), Ok(()));
+        assert_eq!(list.swap(2, 2), Ok(()));
+        assert_eq!(to_vec(&list), vec![4, 3, 2, 1]);
+        assert_eq!(list.last(), Some(&1));
+    }
+
+    #[test]
+    fn test_swap_out_of_range() {
+        let mut list: List<i32> = (1..=2).collect();
+
+        assert_eq!(list.swap(0, 2), Err(ListError::IndexOutOfRange { index: 2, len: 2 }));
+        assert_eq!(list.swap(5, 1), Err(ListError::IndexOutOfRange { index: 5, len: 2 }));
+        assert_eq!(list.swap(3, 3), Err(ListError::IndexOutOfRange { index: 3, len: 2 }));
+        assert_eq!(to_vec(&list), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_clone() {
+        let list: List<String> = ["a", "b"].iter().map(|s| s.to_string()).collect();
+        let mut copy = list.clone();
+        copy.push_back(String::from("c"));
+
+        assert_eq!(to_vec_str(&list), vec!["a", "b"]);
+        assert_eq!(list.last().map(String::as_str), Some("b"));
+        assert_eq!(copy.last().map(String::as_str), Some("c"));
+        assert!(!std::ptr::eq(list.last().unwrap(), copy.get(1).unwrap()));
+    }
+
+    #[test]
+    fn test_debug() {
+        let list: List<i32> = (1..=3).collect();
+        assert_eq!(format!("{:?}", list), "[1, 2, 3]");
+        assert_eq!(format!("{:?}", List::<i32>::new()), "[]");
+        assert_eq!(format!("{:?}", list), format!("{:?}", vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn test_eq_and_ord() {
+        let a: List<i32> = (1..=3).collect();
+        let b: List<i32> = (1..=3).collect();
+        let c: List<i32> = (1..=2).collect();
+        let d: List<i32> = vec![1, 5].into_iter().collect();
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert!(c < a);
+        assert!(a < d);
+        assert_eq!(a.cmp(&d), vec![1, 2, 3].cmp(&vec![1, 5]));
+        assert_eq!(List::<i32>::default(), List::new());
+    }
+
+    #[test]
+    fn test_hash() {
+        use std::collections::HashMap;
+        use std::collections::hash_map::DefaultHasher;
+
+        fn hash_of<H: Hash>(v: &H) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            v.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let a: List<i32> = (1..=3).collect();
+        let b: List<i32> = (1..=3).collect();
+        assert_eq!(hash_of(&a), hash_of(&b));
+
+        let mut map = HashMap::new();
+        map.insert(a, "first");
+        assert_eq!(map.get(&b), Some(&"first"));
+    }
+
+    #[test]
+    fn test_derive_on_containing_struct() {
+        #[derive(Clone, Debug, PartialEq, Eq, Hash, Default)]
+        struct Batch {
+            ids: List<u32>,
+        }
+
+        let batch = Batch { ids: (1..=2).collect() };
+        assert_eq!(batch.clone(), batch);
+        assert_eq!(format!("{:?}", Batch::default()), "Batch { ids: [] }");
+    }
+
+    #[test]
+    fn test_display() {
+        let list: List<i32> = (10..=12).collect();
+        assert_eq!(list.to_string(), "[10 -> 11 -> 12]");
+        assert_eq!(List::<i32>::new().to_string(), "[]");
+        assert_eq!(vec![1].into_iter().collect::<List<i32>>().to_string(), "[1]");
+    }
+
+    #[test]
+    fn test_display_builder() {
+        let list: List<i32> = (1..=5).collect();
+
+        assert_eq!(list.display().separator(", ").to_string(), "[1, 2, 3, 4, 5]");
+        assert_eq!(list.display().brackets("(", ")").separator(" ").to_string(), "(1 2 3 4 5)");
+        assert_eq!(list.display().brackets("", "").to_string(), "1 -> 2 -> 3 -> 4 -> 5");
+    }
+
+    #[test]
+    fn test_display_truncation() {
+        let list: List<i32> = (1..=5).collect();
+
+        assert_eq!(list.display().max_items(2).to_string(), "[1 -> 2 -> … 3 more]");
+        assert_eq!(list.display().max_items(0).to_string(), "[… 5 more]");
+        assert_eq!(list.display().max_items(5).to_string(), "[1 -> 2 -> 3 -> 4 -> 5]");
+        assert_eq!(list.display().max_items(9).to_string(), "[1 -> 2 -> 3 -> 4 -> 5]");
+    }
+
+    #[test]
+    fn test_display_flags_pass_through() {
+        struct Tag(i32);
+
+        impl Display for Tag {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                if f.alternate() {
+                    write!(f, "#{}", self.0)
+                } else {
+                    write!(f, "{}", self.0)
+                }
+            }
+        }
+
+        let list: List<f64> = vec![1.0, 2.5].into_iter().collect();
+        assert_eq!(format!("{:>5.1}", list), "[  1.0 ->   2.5]");
+
+        let tags: List<Tag> = vec![Tag(1), Tag(2)].into_iter().collect();
+        assert_eq!(format!("{:#}", tags), "[#1 -> #2]");
+        assert_eq!(format!("{:#}", tags.display().separator(",")), "[#1,#2]");
+    }
+
+    // The tests below mainly exercise the raw-pointer paths for Miri.
+
+    #[test]
+    fn test_iter_mut_references_are_disjoint() {
+        let mut list: List<i32> = (1..=4).collect();
+        let refs: Vec<&mut i32> = list.iter_mut().collect();
+        for r in refs {
+            *r += 1;
+        }
+        assert_eq!(to_vec(&list), vec![2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_matches_vec_model() {
+        let mut list: List<u32> = List::new();
+        let mut model: Vec<u32> = Vec::new();
+        let mut seed = 7u32;
+
+        for step in 0..400 {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            let r = seed >> 16;
+            let pos = r as usize % (model.len() + 1);
+            match r % 8 {
+                0 => { list.push_front(step); model.insert(0, step); },
+                1 => { list.push_back(step); model.push(step); },
+                2 => { assert_eq!(list.pop_front(), (!model.is_empty()).then(|| model.remove(0))); },
+                3 => { assert_eq!(list.pop_back(), model.pop()); },
+                4 => { list.insert_at(step, pos).unwrap(); model.insert(pos, step); },
+                5 if pos < model.len() => { assert_eq!(list.delete_nth(pos), Ok(model.remove(pos))); },
+                6 => {
+                    let mut cursor = list.cursor_front_mut();
+                    for _ in 0..pos {
+                        cursor.move_next();
+                    }
+                    if cursor.remove_current().is_some() {
+                        model.remove(pos);
+                    }
+                },
+                _ => {
+                    if let Some(v) = list.last_mut() {
+                        *v += 1;
+                        *model.last_mut().unwrap() += 1;
+                    }
+                },
+            }
+            assert_eq!(list.last(), model.last());
+            assert_eq!(list.len(), model.len());
+        }
+
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), model);
+    }
+
+    #[test]
+    fn test_tail_after_relinking() {
+        let mut list: List<i32> = vec![3, 1, 2].into_iter().collect();
+        list.sort();
+        *list.last_mut().unwrap() = 30;
+
+        let mut other = list.split_off(1).unwrap();
+        other.push_back(40);
+        list.append(&mut other);
+        list.push_back(50);
+
+        assert_eq!(to_vec(&list), vec![1, 2, 30, 40, 50]);
+        assert_eq!(other.last(), None);
+    }
+
+    #[test]
+    fn test_send_to_thread() {
+        let list: List<String> = ["a", "b"].iter().map(|s| s.to_string()).collect();
+        let joined = std::thread::spawn(move || {
+            list.iter().map(String::as_str).collect::<Vec<_>>().join(",")
+        }).join().unwrap();
+        assert_eq!(joined, "a,b");
+    }
+
+    #[test]
+    fn test_len() {
+        let mut list: List<i32> = List::new();
+        assert_eq!(list.len(), 0);
+        assert!(list.is_empty());
+
+        list.insert(1);
+        list.push_back(2);
+        list.insert_at(3, 1).unwrap();
+        assert_eq!(list.len(), 3);
+        assert!(!list.is_empty());
+
+        list.pop_back();
+        list.delete_nth(0).unwrap();
+        assert_eq!(list.len(), 1);
+        list.pop_front();
+        assert!(list.is_empty());
+
+        assert!(list.insert_at(1, 1).is_err());
+        assert!(list.delete_nth(0).is_err());
+        assert_eq!(list.len(), 0);
+    }
+
+    #[test]
+    fn test_len_after_bulk_operations() {
+        let mut list: List<i32> = (1..=10).collect();
+        assert_eq!(list.len(), 10);
+
+        let mut suffix = list.split_off(6).unwrap();
+        assert_eq!((list.len(), suffix.len()), (6, 4));
+        list.append(&mut suffix);
+        assert_eq!((list.len(), suffix.len()), (10, 0));
+
+        list.retain(|v| v % 2 == 0);
+        assert_eq!(list.len(), 5);
+        assert_eq!(list.extract_if(|v| *v > 6).count(), 2);
+        assert_eq!(list.len(), 3);
+
+        let mut cursor = list.cursor_front_mut();
+        cursor.insert_before(0);
+        cursor.insert_after(3);
+        cursor.splice_before((7..=8).collect());
+        cursor.splice_after((9..=9).collect());
+        cursor.remove_current();
+        assert_eq!(list.len(), list.iter().count());
+        assert_eq!(list.len(), 7);
+
+        list.dedup_by_key(|v| *v / 100);
+        assert_eq!(list.len(), 1);
+        assert_eq!(list.clone().len(), 1);
+    }
+
+    fn finger_index(list: &List<i32>) -> Option<usize> {
+        list.finger.lock().unwrap().map(|(i, _)| i)
+    }
+
+    #[test]
+    fn test_finger_sequential_access() {
+        let list: List<i32> = (0..100).collect();
+        for i in 0..100 {
+            assert_eq!(list[i], i as i32);
+            assert_eq!(finger_index(&list), Some(i));
+        }
+        assert_eq!(list[3], 3);
+        assert_eq!(list.get(100), None);
+        assert_eq!(finger_index(&list), Some(3));
+    }
+
+    #[test]
+    fn test_finger_invalidated_by_insert() {
+        let mut list: List<i32> = (0..5).collect();
+        assert_eq!(list[3], 3);
+
+        list.insert(-1);
+        assert_eq!(finger_index(&list), None);
+        assert_eq!(list[3], 2);
+        assert_eq!(list[4], 3);
+    }
+
+    #[test]
+    fn test_finger_invalidated_by_insert_at() {
+        let mut list: List<i32> = (0..5).collect();
+        assert_eq!(list[3], 3);
+
+        list.insert_at(10, 2).unwrap();
+        assert_eq!(list[3], 2);
+        list.insert_at(11, 4).unwrap();
+        assert_eq!(list[3], 2);
+        assert_eq!(list[4], 11);
+        assert_eq!(to_vec(&list), vec![0, 1, 10, 2, 11, 3, 4]);
+    }
+
+    #[test]
+    fn test_finger_invalidated_by_delete_nth() {
+        let mut list: List<i32> = (0..5).collect();
+        assert_eq!(list[4], 4);
+        assert_eq!(list[3], 3);
+
+        assert_eq!(list.delete_nth(3), Ok(3));
+        assert_eq!(list[3], 4);
+        assert_eq!(list.delete_nth(0), Ok(0));
+        assert_eq!(list[0], 1);
+        assert_eq!(list[2], 4);
+        assert_eq!(list.pop_back(), Some(4));
+        assert_eq!(list.get(2), None);
+        assert_eq!(list[1], 2);
+    }
+
+    #[test]
+    fn test_finger_invalidated_by_relinking() {
+        let mut list: List<i32> = vec![3, 2, 1, 0].into_iter().collect();
+        assert_eq!(list[2], 1);
+        list.sort();
+        assert_eq!(list[2], 2);
+
+        list.cursor_front_mut().remove_current();
+        assert_eq!(list[2], 3);
+
+        let suffix = list.split_off(1).unwrap();
+        assert_eq!(list.get(2), None);
+        assert_eq!(suffix[1], 3);
+
+        list.retain(|_| false);
+        assert_eq!(list.get(0), None);
+    }
+
+    // Sorts `values` with a comparator that panics on call `panic_at`, and
+    // checks the list is still whole and usable afterwards.
+    fn sort_panicking_at(values: &[i32], panic_at: usize) -> List<i32> {
+        let mut list: List<i32> = values.iter().copied().collect();
+        let mut calls = 0;
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            list.sort_by(|a, b| {
+                calls += 1;
+                assert!(calls != panic_at, "comparator panic");
+                a.cmp(b)
+            });
+        }));
+        assert!(result.is_err());
+
+        let mut seen = to_vec(&list);
+        assert_eq!(list.len(), seen.len());
+        assert_eq!(list.last(), seen.last());
+        seen.sort();
+        let mut expected = values.to_vec();
+        expected.sort();
+        assert_eq!(seen, expected);
+        list
+    }
+
+    #[test]
+    fn test_sort_panicking_comparator_keeps_list_whole() {
+        let mut list = sort_panicking_at(&[2, 4, 6, 1, 5, 3], 8);
+        list.pop_front();
+        list.pop_front();
+        assert_eq!(list.len(), 4);
+        assert!(list.last().is_some());
+        list.push_back(7);
+        assert_eq!(list.last(), Some(&7));
+
+        // Every call the comparator makes is a possible panic point.
+        let values = [5, 1, 4, 1, 5, 9, 2, 6, 5, 3];
+        for panic_at in 1..=20 {
+            let mut list = sort_panicking_at(&values, panic_at);
+            list.sort();
+            assert_eq!(to_vec(&list), vec![1, 1, 2, 3, 4, 5, 5, 5, 6, 9]);
+        }
+    }
+
+    #[test]
+    fn test_finger_cleared_by_append() {
+        let mut a: List<i32> = (0..3).collect();
+        let mut b: List<i32> = (-3..0).collect();
+        assert_eq!(b[0], -3);
+
+        a.append(&mut b);
+        assert_eq!(finger_index(&b), None);
+        drop(a);
+
+        // A stale finger would make this read a freed node of `a`.
+        b.push_back(100);
+        b.push_back(200);
+        assert_eq!(b[0], 100);
+    }
+
+    #[test]
+    fn test_finger_moves_with_split_off_at_zero() {
+        let mut a: List<i32> = (0..4).collect();
+        assert_eq!(a[1], 1);
+
+        let b = a.split_off(0).unwrap();
+        assert_eq!(finger_index(&a), None);
+        assert_eq!(finger_index(&b), Some(1));
+        a.push_back(10);
+        a.push_back(11);
+        assert_eq!(a[0], 10);
+        assert_eq!(b[2], 2);
+    }
+
+    #[test]
+    fn test_finger_get_mut() {
+        let mut list: List<i32> = (0..5).collect();
+        for i in 0..5 {
+            list[i] *= 2;
+        }
+        assert_eq!(finger_index(&list), Some(4));
+        assert_eq!(to_vec(&list), vec![0, 2, 4, 6, 8]);
+    }
+
+    #[test]
+    fn test_reverse() {
+        let mut list: List<i32> = (1..=4).collect();
+        list.reverse();
+
+        assert_eq!(to_vec(&list), vec![4, 3, 2, 1]);
+        assert_eq!(list.first(), Some(&4));
+        assert_eq!(list.last(), Some(&1));
+        list.push_back(0);
+        assert_eq!(to_vec(&list), vec![4, 3, 2, 1, 0]);
+
+        let mut single: List<i32> = vec![1].into_iter().collect();
+        single.reverse();
+        assert_eq!(single.last(), Some(&1));
+        let mut empty: List<i32> = List::new();
+        empty.reverse();
+        assert_eq!(empty.last(), None);
+    }
+
+    #[test]
+    fn test_insert_then_reverse_keeps_source_order() {
+        let mut list = List::new();
+        for v in 1..=3 {
+            list.insert(v);
+        }
+        list.reverse();
+        assert_eq!(to_vec(&list), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_rotate_left() {
+        let mut list: List<i32> = (1..=5).collect();
+        list.rotate_left(2);
+        assert_eq!(to_vec(&list), vec![3, 4, 5, 1, 2]);
+        assert_eq!(list.last(), Some(&2));
+
+        list.rotate_left(5);
+        assert_eq!(to_vec(&list), vec![3, 4, 5, 1, 2]);
+        list.rotate_left(9);
+        assert_eq!(to_vec(&list), vec![2, 3, 4, 5, 1]);
+        assert_eq!(list[4], 1);
+        list.push_back(6);
+        assert_eq!(to_vec(&list), vec![2, 3, 4, 5, 1, 6]);
+    }
+
+    #[test]
+    fn test_rotate_right() {
+        let mut list: List<i32> = (1..=5).collect();
+        list.rotate_right(2);
+        assert_eq!(to_vec(&list), vec![4, 5, 1, 2, 3]);
+        assert_eq!(list.first(), Some(&4));
+        assert_eq!(list.last(), Some(&3));
+
+        list.rotate_right(0);
+        assert_eq!(to_vec(&list), vec![4, 5, 1, 2, 3]);
+
+        let mut empty: List<i32> = List::new();
+        empty.rotate_right(3);
+        empty.rotate_left(3);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_reverse_k_groups() {
+        let mut list: List<i32> = (1..=6).collect();
+        list.reverse_k_groups(2);
+        assert_eq!(to_vec(&list), vec![2, 1, 4, 3, 6, 5]);
+        assert_eq!(list.last(), Some(&5));
+        list.push_back(7);
+        assert_eq!(list.last(), Some(&7));
+
+        let mut list: List<i32> = (1..=8).collect();
+        list.reverse_k_groups(3);
+        assert_eq!(to_vec(&list), vec![3, 2, 1, 6, 5, 4, 7, 8]);
+        assert_eq!(list.last(), Some(&8));
+        assert_eq!(list.len(), 8);
+    }
+
+    #[test]
+    fn test_reverse_k_groups_edges() {
+        let mut list: List<i32> = (1..=3).collect();
+        list.reverse_k_groups(0);
+        list.reverse_k_groups(1);
+        list.reverse_k_groups(4);
+        assert_eq!(to_vec(&list), vec![1, 2, 3]);
+
+        list.reverse_k_groups(3);
+        assert_eq!(to_vec(&list), vec![3, 2, 1]);
+        assert_eq!(list.first(), Some(&3));
+        assert_eq!(list.last(), Some(&1));
+    }
+
+    #[test]
+    fn test_contains_position_find() {
+        let list: List<i32> = vec![5, 7, 9, 7].into_iter().collect();
+
+        assert!(list.contains(&9));
+        assert!(!list.contains(&1));
+        assert_eq!(list.position(|v| *v == 7), Some(1));
+        assert_eq!(list.position(|v| *v > 9), None);
+        assert_eq!(list.find(|v| *v > 6), Some(&7));
+        assert_eq!(list.find(|v| *v > 9), None);
+    }
+
+    #[test]
+    fn test_remove_first() {
+        let mut list: List<i32> = vec![1, 2, 3, 2].into_iter().collect();
+
+        assert_eq!(list.remove_first(&2), Some(2));
+        assert_eq!(to_vec(&list), vec![1, 3, 2]);
+        assert_eq!(list.remove_first(&2), Some(2));
+        assert_eq!(list.last(), Some(&3));
+        assert_eq!(list.remove_first(&4), None);
+        assert_eq!(list.len(), 2);
+        list.push_back(4);
+        assert_eq!(to_vec(&list), vec![1, 3, 4]);
+    }
+
+    #[test]
+    fn test_remove_all() {
+        let mut list: List<i32> = vec![2, 1, 2, 3, 2].into_iter().collect();
+
+        assert_eq!(list.remove_all(&2), 3);
+        assert_eq!(to_vec(&list), vec![1, 3]);
+        assert_eq!(list.last(), Some(&3));
+        assert_eq!(list.remove_all(&2), 0);
+        assert_eq!(list.remove_all(&1), 1);
+        assert_eq!(list.remove_all(&3), 1);
+        assert!(list.is_empty());
+        assert_eq!(list.last(), None);
     }
 }
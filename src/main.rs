@@ -1,7 +1,6 @@
-mod linked_list;
 mod bstree;
 
-use linked_list::List;
+use linkedlist_rust::linked_list::List;
 use linkedlist_rust::doubly_linked_list::Dlist;
 use bstree::Bstree;
 
@@ -26,6 +25,13 @@ fn main() {
 
     println!("{}", list);
 
+    let mut names: List<String> = List::new();
+    names.insert(String::from("world"));
+    names.insert(String::from("hello"));
+
+    println!("{}", names);
+    println!("{}", list.display().separator(", ").max_items(2));
+
     print!("doubly linked list:\n\n");
 
     let mut dlist :Dlist<i32> = Dlist::new();
@@ -0,0 +1,255 @@
+use std::rc::Rc;
+
+// Defines a persistent list type `$name` whose cells are shared through
+// `$ptr` (`Rc` or `Arc`), along with its `Node`, `Iter` and trait impls.
+// `Drop` is left to the caller, since freeing a chain safely depends on the
+// pointer type.
+macro_rules! persistent_list {
+    ($(#[$attr:meta])* $name:ident, $ptr:ident) => {
+        $(#[$attr])*
+        pub struct $name<T> {
+            head: $ptr<Node<T>>,
+            len: usize,
+        }
+
+        enum Node<T> {
+            Val { value: T, next: $ptr<Node<T>> },
+            Nil,
+        }
+
+        impl<T> $name<T> {
+            pub fn new() -> $name<T> {
+                $name { head: $ptr::new(Node::Nil), len: 0 }
+            }
+
+            pub fn len(&self) -> usize {
+                self.len
+            }
+
+            pub fn is_empty(&self) -> bool {
+                self.len == 0
+            }
+
+            /// Returns a new list with `value` in front of this one, sharing all of
+            /// this list's cells.
+            pub fn push_front(&self, value: T) -> $name<T> {
+                $name {
+                    head: $ptr::new(Node::Val { value, next: $ptr::clone(&self.head) }),
+                    len: self.len + 1,
+                }
+            }
+
+            /// Returns the first element.
+            pub fn head(&self) -> Option<&T> {
+                let Node::Val { ref value, .. } = *self.head else {
+                    return None;
+                };
+                Some(value)
+            }
+
+            /// Returns the list without its first element, sharing its cells, or
+            /// `None` if the list is empty.
+            pub fn tail(&self) -> Option<$name<T>> {
+                let Node::Val { ref next, .. } = *self.head else {
+                    return None;
+                };
+                Some($name { head: $ptr::clone(next), len: self.len - 1 })
+            }
+
+            /// Returns `true` if both lists are the same version, i.e. share their
+            /// first cell.
+            pub fn ptr_eq(&self, other: &$name<T>) -> bool {
+                $ptr::ptr_eq(&self.head, &other.head)
+            }
+
+            pub fn iter(&self) -> Iter<'_, T> {
+                Iter { node: &self.head }
+            }
+        }
+
+        impl<T> Default for $name<T> {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl<T> Clone for $name<T> {
+            fn clone(&self) -> Self {
+                $name { head: $ptr::clone(&self.head), len: self.len }
+            }
+        }
+
+        pub struct Iter<'a, T> {
+            node: &'a Node<T>,
+        }
+
+        impl<'a, T> Iterator for Iter<'a, T> {
+            type Item = &'a T;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                let Node::Val { ref value, ref next } = *self.node else {
+                    return None;
+                };
+                self.node = next;
+                Some(value)
+            }
+        }
+
+        impl<'a, T> IntoIterator for &'a $name<T> {
+            type Item = &'a T;
+            type IntoIter = Iter<'a, T>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.iter()
+            }
+        }
+
+        impl<T> FromIterator<T> for $name<T> {
+            /// Builds a list with the items in iteration order.
+            fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+                let items: Vec<T> = iter.into_iter().collect();
+                items.into_iter().rev().fold($name::new(), |list, v| list.push_front(v))
+            }
+        }
+
+        impl<T: std::fmt::Debug> std::fmt::Debug for $name<T> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_list().entries(self.iter()).finish()
+            }
+        }
+
+        impl<T: PartialEq> PartialEq for $name<T> {
+            fn eq(&self, other: &Self) -> bool {
+                self.len == other.len && self.iter().eq(other.iter())
+            }
+        }
+
+        impl<T: Eq> Eq for $name<T> {}
+
+        impl<T :std::fmt::Display> std::fmt::Display for $name<T> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str("[")?;
+                for (i, value) in self.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(" -> ")?;
+                    }
+                    value.fmt(f)?;
+                }
+                f.write_str("]")
+            }
+        }
+    };
+}
+
+pub(crate) use persistent_list;
+
+persistent_list! {
+    /// An immutable singly linked list whose versions share structure.
+    ///
+    /// Cells are `Val { value, next }` nodes ending in a `Nil` cell, with `next`
+    /// an `Rc`, so [`Plist::push_front`] builds a new version on top of an
+    /// existing one in O(1) without copying it, and old versions stay valid.
+    /// Cloning a `Plist` is O(1).
+    Plist, Rc
+}
+
+impl<T> Drop for Plist<T> {
+    // Free the cells only this version owns, one at a time, so dropping a long
+    // list does not recurse. Stops at the first cell still shared with another
+    // version.
+    fn drop(&mut self) {
+        let mut node = std::mem::replace(&mut self.head, Rc::new(Node::Nil));
+        while let Ok(Node::Val { next, .. }) = Rc::try_unwrap(node) {
+            node = next;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_vec(list: &Plist<i32>) -> Vec<i32> {
+        list.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_new() {
+        let list: Plist<i32> = Plist::new();
+        assert!(list.is_empty());
+        assert_eq!(list.head(), None);
+        assert!(list.tail().is_none());
+    }
+
+    #[test]
+    fn test_push_front_keeps_old_version() {
+        let a = Plist::new().push_front(2).push_front(1);
+        let b = a.push_front(0);
+
+        assert_eq!(to_vec(&a), vec![1, 2]);
+        assert_eq!(to_vec(&b), vec![0, 1, 2]);
+        assert_eq!((a.len(), b.len()), (2, 3));
+    }
+
+    #[test]
+    fn test_structural_sharing() {
+        let base: Plist<i32> = (1..=3).collect();
+        let x = base.push_front(10);
+        let y = base.push_front(20);
+
+        assert!(x.tail().unwrap().ptr_eq(&base));
+        assert!(y.tail().unwrap().ptr_eq(&base));
+        assert!(!x.ptr_eq(&y));
+        assert!(base.clone().ptr_eq(&base));
+    }
+
+    #[test]
+    fn test_head_and_tail() {
+        let list: Plist<String> = ["a", "b"].iter().map(|s| s.to_string()).collect();
+
+        assert_eq!(list.head().map(String::as_str), Some("a"));
+        let rest = list.tail().unwrap();
+        assert_eq!(rest.head().map(String::as_str), Some("b"));
+        assert_eq!(rest.len(), 1);
+        assert!(rest.tail().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_undo_with_snapshots() {
+        let mut history = vec![Plist::new()];
+        for v in 1..=3 {
+            let next = history.last().unwrap().push_front(v);
+            history.push(next);
+        }
+
+        history.pop();
+        assert_eq!(to_vec(history.last().unwrap()), vec![2, 1]);
+    }
+
+    #[test]
+    fn test_drop_keeps_shared_cells() {
+        let base: Plist<i32> = (1..=3).collect();
+        let longer = base.push_front(0);
+        drop(base);
+
+        assert_eq!(to_vec(&longer), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_drop_long_list() {
+        let n = if cfg!(miri) { 1_000 } else { 2_000_000 };
+        let list = (0..n).fold(Plist::new(), |list, v| list.push_front(v));
+        drop(list);
+    }
+
+    #[test]
+    fn test_eq_debug_display() {
+        let a: Plist<i32> = (1..=3).collect();
+        let b = Plist::new().push_front(3).push_front(2).push_front(1);
+
+        assert_eq!(a, b);
+        assert_ne!(a, b.tail().unwrap());
+        assert_eq!(format!("{:?}", a), "[1, 2, 3]");
+        assert_eq!(a.to_string(), "[1 -> 2 -> 3]");
+    }
+}